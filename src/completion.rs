@@ -0,0 +1,144 @@
+use crate::locale::Locale;
+use crate::parse_error::{record_expectations, Expected};
+use crate::parser::{english, productions};
+
+const MAX_WEEK_NUMBER: u32 = 53;
+
+// A way of continuing a partial input
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Completion {
+    // The top-level production `text` belongs to, as named in parse errors
+    pub production: &'static str,
+    // Where `text` starts in the input.  The input from there on is a prefix of `text`.
+    pub offset: usize,
+    pub text: String,
+}
+
+fn candidates(locale: &Locale, expected: &Expected, typed: &str) -> Vec<String> {
+    match expected {
+        Expected::Token(token) if token.starts_with(typed) && !token.trim().is_empty() => {
+            vec![token.to_string()]
+        }
+        Expected::MonthName => locale
+            .complete_month(typed)
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+        Expected::WeekdayName => locale
+            .complete_weekday(typed)
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+        Expected::WeekNumber => (1..=MAX_WEEK_NUMBER)
+            .map(|week| week.to_string())
+            .filter(|week| week.starts_with(typed))
+            .collect(),
+        // Arbitrary numbers and whitespace aren't worth offering
+        _ => Vec::new(),
+    }
+}
+
+// Everything the grammar would accept at the point where `input` runs out: "se" may go on as
+// "sep" or "september", "+3" as "+3d" or "+3fri".  A word typed halfway is completed as a
+// whole, so each completion replaces the input from its offset on.  Completions come grouped
+// by production, in the order parse() tries them.
+pub fn complete_with_locale(input: &str, locale: &Locale) -> Vec<Completion> {
+    let mut result: Vec<Completion> = Vec::new();
+    for (production, parser) in productions(locale) {
+        let (_, expectations) = record_expectations(|| parser(input));
+        for (remaining, expected) in expectations {
+            let offset = input.len() - remaining;
+            let typed = &input[offset..];
+            // Only a word still being typed can be completed, not one followed by anything else
+            if typed.contains(char::is_whitespace) {
+                continue;
+            }
+            for text in candidates(locale, &expected, &typed.to_lowercase()) {
+                let completion = Completion {
+                    production,
+                    offset,
+                    text,
+                };
+                if !result.contains(&completion) {
+                    result.push(completion);
+                }
+            }
+        }
+    }
+    result
+}
+
+pub fn complete(input: &str) -> Vec<Completion> {
+    complete_with_locale(input, english())
+}
+
+#[cfg(test)]
+mod test_complete {
+    use super::*;
+
+    fn texts<'a>(completions: &'a [Completion], production: &str) -> Vec<&'a str> {
+        completions
+            .iter()
+            .filter(|completion| completion.production == production)
+            .map(|completion| completion.text.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_month_name() {
+        let completions = complete("se");
+        assert_eq!(
+            texts(&completions, "month and day"),
+            vec!["september", "sep"]
+        );
+        assert!(completions.iter().all(|completion| completion.offset == 0));
+    }
+
+    #[test]
+    fn test_week_number() {
+        let completions = complete("2026-w");
+        let weeks = texts(&completions, "ISO week date");
+        assert_eq!(weeks.len(), 53);
+        assert_eq!(weeks[0], "1");
+        assert_eq!(weeks[52], "53");
+        assert!(completions.iter().all(|completion| completion.offset == 6));
+    }
+
+    #[test]
+    fn test_relative_units_and_weekdays() {
+        let completions = complete("+3");
+        let offsets = texts(&completions, "offset into the future");
+        for unit in ["h", "d", "w", "m", "y", "monday", "fri"] {
+            assert!(
+                offsets.contains(&unit),
+                "{} missing from {:?}",
+                unit,
+                offsets
+            );
+        }
+        assert!(completions.iter().all(|completion| completion.offset == 2));
+    }
+
+    #[test]
+    fn test_keyword() {
+        assert_eq!(
+            complete("tom"),
+            vec![Completion {
+                production: "keyword",
+                offset: 0,
+                text: "tomorrow".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_other_locale() {
+        let completions = complete_with_locale("Mä", &Locale::german());
+        assert_eq!(texts(&completions, "month and day"), vec!["märz", "mär"]);
+    }
+
+    #[test]
+    fn test_nothing_to_offer() {
+        assert_eq!(complete("xyz"), vec![]);
+    }
+}
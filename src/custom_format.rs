@@ -0,0 +1,164 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::error::{OrgModeDateTimeError, Result};
+use crate::timestamp::{Timestamp, TimestampKind};
+
+// Equivalent of `org-time-stamp-custom-formats`: strftime-like templates for timestamps with and
+// without a time of day.  As in Org, the brackets in a template are only there for show: the
+// timestamp keeps its own.
+// https://orgmode.org/manual/Custom-time-format.html
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CustomFormats {
+    pub date: String,
+    pub date_time: String,
+}
+
+impl Default for CustomFormats {
+    fn default() -> Self {
+        CustomFormats {
+            date: "<%m/%d/%y %a>".to_string(),
+            date_time: "<%m/%d/%y %a %H:%M>".to_string(),
+        }
+    }
+}
+
+fn strip_brackets(template: &str) -> &str {
+    let stripped = template
+        .strip_prefix(['<', '['])
+        .and_then(|rest| rest.strip_suffix(['>', ']']));
+    stripped.unwrap_or(template)
+}
+
+fn validated_template(template: &str) -> Result<&str> {
+    let template = strip_brackets(template);
+    if StrftimeItems::new(template).any(|item| item == Item::Error) {
+        return Err(OrgModeDateTimeError::InvalidCustomFormat(
+            template.to_string(),
+        ));
+    }
+    Ok(template)
+}
+
+// Formats the date and start time of `timestamp`; like `org-display-custom-times`, it leaves out
+// the end time, repeater and delay
+pub fn format_custom_timestamp(timestamp: &Timestamp, formats: &CustomFormats) -> Result<String> {
+    let (open, close) = match timestamp.kind {
+        TimestampKind::Active => ('<', '>'),
+        TimestampKind::Inactive => ('[', ']'),
+    };
+    let body = match timestamp.start_time {
+        Some(time) => timestamp
+            .date
+            .and_time(time)
+            .format(validated_template(&formats.date_time)?)
+            .to_string(),
+        None => timestamp
+            .date
+            .format(validated_template(&formats.date)?)
+            .to_string(),
+    };
+    Ok(format!("{}{}{}", open, body, close))
+}
+
+// The inverse of `format_custom_timestamp`.  Only succeeds if the template pins down the whole
+// date (and, for the date-time template, the time of day): "%a %d %b" has no year and never
+// parses back.
+pub fn parse_custom_timestamp(input: &str, formats: &CustomFormats) -> Result<Timestamp> {
+    let (kind, body) = match (input.chars().next(), input.chars().last()) {
+        (Some('<'), Some('>')) => (TimestampKind::Active, &input[1..input.len() - 1]),
+        (Some('['), Some(']')) => (TimestampKind::Inactive, &input[1..input.len() - 1]),
+        _ => return Err(OrgModeDateTimeError::MissingTimestampBrackets),
+    };
+    let timestamp = |date, start_time| Timestamp {
+        kind,
+        date,
+        start_time,
+        end_time: None,
+        repeater: None,
+        delay: None,
+    };
+    let date_time_template = validated_template(&formats.date_time)?;
+    if let Ok(datetime) = NaiveDateTime::parse_from_str(body, date_time_template) {
+        return Ok(timestamp(datetime.date(), Some(datetime.time())));
+    }
+    let date = NaiveDate::parse_from_str(body, validated_template(&formats.date)?)?;
+    Ok(timestamp(date, None))
+}
+
+#[cfg(test)]
+mod test_custom_format {
+    use chrono::NaiveTime;
+
+    use super::*;
+    use crate::timestamp::parse_timestamp;
+
+    fn european() -> CustomFormats {
+        CustomFormats {
+            date: "<%a %d %b %Y>".to_string(),
+            date_time: "<%a %d %b %Y %H:%M>".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_format_date() {
+        let (_, timestamp) = parse_timestamp("<2006-06-13 Tue>").unwrap();
+        assert_eq!(
+            format_custom_timestamp(&timestamp, &european()).unwrap(),
+            "<Tue 13 Jun 2006>"
+        );
+        assert_eq!(
+            format_custom_timestamp(&timestamp, &CustomFormats::default()).unwrap(),
+            "<06/13/06 Tue>"
+        );
+    }
+
+    #[test]
+    fn test_format_keeps_brackets() {
+        let (_, timestamp) = parse_timestamp("[2006-06-13 Tue 10:00]").unwrap();
+        assert_eq!(
+            format_custom_timestamp(&timestamp, &european()).unwrap(),
+            "[Tue 13 Jun 2006 10:00]"
+        );
+    }
+
+    #[test]
+    fn test_parse_back() {
+        let timestamp = parse_custom_timestamp("<Tue 13 Jun 2006>", &european()).unwrap();
+        assert_eq!(timestamp.kind, TimestampKind::Active);
+        assert_eq!(
+            timestamp.date,
+            NaiveDate::from_ymd_opt(2006, 6, 13).unwrap()
+        );
+        assert_eq!(timestamp.start_time, None);
+
+        let timestamp = parse_custom_timestamp("[Tue 13 Jun 2006 10:00]", &european()).unwrap();
+        assert_eq!(timestamp.kind, TimestampKind::Inactive);
+        assert_eq!(timestamp.start_time, NaiveTime::from_hms_opt(10, 0, 0));
+    }
+
+    #[test]
+    fn test_parse_back_ambiguous() {
+        let formats = CustomFormats {
+            date: "<%a %d %b>".to_string(),
+            date_time: "<%a %d %b %H:%M>".to_string(),
+        };
+        assert!(matches!(
+            parse_custom_timestamp("<Tue 13 Jun>", &formats),
+            Err(OrgModeDateTimeError::UnparseableCustomTimestamp(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_template() {
+        let (_, timestamp) = parse_timestamp("<2006-06-13 Tue>").unwrap();
+        let formats = CustomFormats {
+            date: "<%Q>".to_string(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            format_custom_timestamp(&timestamp, &formats),
+            Err(OrgModeDateTimeError::InvalidCustomFormat(_))
+        ));
+    }
+}
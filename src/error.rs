@@ -1,8 +1,13 @@
 use std::num::TryFromIntError;
 
+use chrono::NaiveDateTime;
 use thiserror::Error;
 
+use crate::parse_error::{describe_expected, describe_suggestions, Expected};
 use crate::parser::Relative;
+use crate::renderer::RenderedSpec;
+use crate::span::Field;
+use crate::validation::describe_fields;
 
 pub type Result<T> = std::result::Result<T, OrgModeDateTimeError>;
 
@@ -14,4 +19,36 @@ pub enum OrgModeDateTimeError {
     UnrepresentablePastRelativeDate(Relative),
     #[error("Unrepresentable future date")]
     UnrepresentableFutureRelativeDate(Relative),
+    #[error("Unrepresentable as an Org timestamp")]
+    UnrepresentableTimestamp(RenderedSpec),
+    #[error("Invalid custom timestamp format: {0}")]
+    InvalidCustomFormat(String),
+    #[error("Timestamp not enclosed in <> or []")]
+    MissingTimestampBrackets,
+    #[error("Timestamp doesn't match the custom format")]
+    UnparseableCustomTimestamp(#[from] chrono::ParseError),
+    #[error(
+        "Unexpected input at offset {offset}, expected {}{}",
+        describe_expected(.expected),
+        describe_suggestions(.suggestions)
+    )]
+    UnexpectedInput {
+        offset: usize,
+        expected: Vec<Expected>,
+        productions: Vec<&'static str>,
+        // Names the input may be a misspelling of, closest first
+        suggestions: Vec<String>,
+    },
+    #[error("Unexpected {remainder:?} at offset {offset} after a complete date/time")]
+    TrailingInput { offset: usize, remainder: String },
+    #[error("{field} {value} is out of range")]
+    FieldOutOfRange { field: Field, value: u32 },
+    #[error("Unsupported combination of fields: {}", describe_fields(.0))]
+    UnsupportedFields(Vec<Field>),
+    #[error("Date outside the range chrono can represent")]
+    DateOutOfRange,
+    #[error("{0} doesn't exist in the time zone")]
+    NonexistentLocalTime(NaiveDateTime),
+    #[error("{0} is ambiguous in the time zone")]
+    AmbiguousLocalTime(NaiveDateTime),
 }
@@ -0,0 +1,172 @@
+use chrono::NaiveDateTime;
+
+use crate::error::Result;
+use crate::locale::Locale;
+use crate::parser::{alternative_productions, english, productions, DateTimeSpec};
+use crate::renderer::{render, RenderedSpec};
+
+// One way of reading an input, as found by `parse_all()`
+#[derive(Debug)]
+pub struct Interpretation {
+    pub production: &'static str,
+    pub spec: DateTimeSpec,
+    pub rendered: Result<RenderedSpec>,
+}
+
+// Every production that matches the whole of `input` (give or take surrounding whitespace), in
+// the order parse() tries them, followed by the readings parse() never picks: "2/5" as the 2nd
+// of May, "3-2-5" with the abbreviated year last, "12" as noon.  Readings yielding the same spec
+// as an earlier one are left out, so a single interpretation means the input is unambiguous.
+pub fn parse_all_with_locale(
+    input: &str,
+    locale: &Locale,
+    default: NaiveDateTime,
+    now: NaiveDateTime,
+) -> Vec<Interpretation> {
+    let input = input.trim();
+    let mut result: Vec<Interpretation> = Vec::new();
+    for (production, parser) in productions(locale)
+        .into_iter()
+        .chain(alternative_productions())
+    {
+        let spec = match parser(input) {
+            Ok(("", spec)) => spec,
+            _ => continue,
+        };
+        if result.iter().any(|known| known.spec == spec) {
+            continue;
+        }
+        let rendered = render(default, now, spec.clone());
+        result.push(Interpretation {
+            production,
+            spec,
+            rendered,
+        });
+    }
+    result
+}
+
+pub fn parse_all(input: &str, default: NaiveDateTime, now: NaiveDateTime) -> Vec<Interpretation> {
+    parse_all_with_locale(input, english(), default, now)
+}
+
+#[cfg(test)]
+mod test_parse_all {
+    use chrono::{NaiveDate, NaiveTime, Timelike};
+
+    use super::*;
+    use crate::parser::Absolute;
+
+    fn at(year: i32, month: u32, day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    }
+
+    fn summary(interpretations: &[Interpretation]) -> Vec<(&'static str, RenderedSpec)> {
+        interpretations
+            .iter()
+            .map(|interpretation| {
+                let rendered = match &interpretation.rendered {
+                    Ok(RenderedSpec::Date(date)) => RenderedSpec::Date(*date),
+                    other => panic!("unexpected rendering: {:?}", other),
+                };
+                (interpretation.production, rendered)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_month_day_order() {
+        let now = at(2006, 1, 1);
+        let interpretations = parse_all("2/5", now, now);
+        assert_eq!(
+            summary(&interpretations),
+            vec![
+                (
+                    "slashed date",
+                    RenderedSpec::Date(NaiveDate::from_ymd_opt(2006, 2, 5).unwrap())
+                ),
+                (
+                    "day-first slashed date",
+                    RenderedSpec::Date(NaiveDate::from_ymd_opt(2006, 5, 2).unwrap())
+                ),
+            ]
+        );
+        assert_eq!(
+            interpretations[1].spec,
+            DateTimeSpec::Absolute(Absolute {
+                month: Some(5),
+                day: Some(2),
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_same_reading_either_way() {
+        let now = at(2006, 1, 1);
+        assert_eq!(parse_all("2/2", now, now).len(), 1);
+    }
+
+    #[test]
+    fn test_abbreviated_year() {
+        let now = at(2006, 1, 1);
+        assert_eq!(
+            summary(&parse_all(" 3-2-5 ", now, now)),
+            vec![
+                (
+                    "dashed date",
+                    RenderedSpec::Date(NaiveDate::from_ymd_opt(2003, 2, 5).unwrap())
+                ),
+                (
+                    "month-first dashed date",
+                    RenderedSpec::Date(NaiveDate::from_ymd_opt(2005, 3, 2).unwrap())
+                ),
+                (
+                    "day-first dashed date",
+                    RenderedSpec::Date(NaiveDate::from_ymd_opt(2005, 2, 3).unwrap())
+                ),
+            ]
+        );
+        // Neither a full year first nor a long one last is abbreviated
+        assert_eq!(parse_all("2006-2-5", now, now).len(), 1);
+        assert_eq!(parse_all("3-2-2005", now, now).len(), 1);
+    }
+
+    #[test]
+    fn test_day_or_hour() {
+        let now = at(2006, 1, 1);
+        let interpretations = parse_all("12", now, now);
+        let productions: Vec<&str> = interpretations
+            .iter()
+            .map(|interpretation| interpretation.production)
+            .collect();
+        assert_eq!(productions, vec!["day of month or time", "hour"]);
+        assert!(matches!(
+            interpretations[0].rendered,
+            Ok(RenderedSpec::Date(date)) if date == NaiveDate::from_ymd_opt(2006, 1, 12).unwrap()
+        ));
+        let noon = at(2006, 1, 1).with_hour(12).unwrap();
+        assert!(matches!(
+            interpretations[1].rendered,
+            Ok(RenderedSpec::DateTime(datetime)) if datetime == noon
+        ));
+        // No hour is 31
+        assert_eq!(parse_all("31", now, now).len(), 1);
+    }
+
+    #[test]
+    fn test_partial_matches_left_out() {
+        let now = at(2006, 1, 1);
+        let interpretations = parse_all("12:45", now, now);
+        assert_eq!(interpretations.len(), 1);
+        assert_eq!(interpretations[0].production, "day of month or time");
+        assert!(matches!(
+            interpretations[0].rendered,
+            Ok(RenderedSpec::DateTime(datetime)) if datetime.time() == NaiveTime::from_hms_opt(12, 45, 0).unwrap()
+        ));
+        assert!(parse_all("xyz", now, now).is_empty());
+    }
+}
@@ -1,14 +1,55 @@
 //! A [nom](https://crates.io/crates/nom)-based Recursive Descent Parser and
 //! renderer for human-friendly date/time input as supported by Emacs'
 //! [Org-mode](https://orgmode.org/manual/The-date_002ftime-prompt.html).
+//!
+//! With the `serde` feature, [`DateTimeSpec`], its payload structs, [`Meridiem`] and
+//! [`RenderedSpec`] implement `Serialize` and `Deserialize`.  Enums with payloads are
+//! adjacently tagged, with the snake_case variant name under `"kind"` and the payload under
+//! `"value"`; tuple payloads are arrays.  Structs are objects whose unset fields are `null`.
+//! Meridiems are `"am"` and `"pm"`.  Chrono values are ISO 8601 strings: `"2006-06-13"`,
+//! `"14:00:00"`, `"2006-06-13T14:00:00"`.  For example, `sep 15` parses into
+//!
+//! ```json
+//! {"kind": "absolute", "value": {"year": null, "month": 9, "day": 15, "week": null,
+//!  "weekday": null, "hour": null, "minute": null, "meridiem": null, "zone": null}}
+//! ```
+mod completion;
+mod custom_format;
 mod error;
+mod interpretation;
+mod locale;
+mod parse_error;
 mod parser;
+mod printer;
 mod renderer;
+mod span;
+mod timestamp;
+mod validation;
+mod zoned;
 
+pub use completion::{complete, complete_with_locale, Completion};
+pub use custom_format::{format_custom_timestamp, parse_custom_timestamp, CustomFormats};
 pub use error::{OrgModeDateTimeError, Result};
+pub use interpretation::{parse_all, parse_all_with_locale, Interpretation};
+pub use locale::Locale;
 pub use nom::IResult;
-pub use parser::{parse, Absolute, AbsoluteTime, DateTimeSpec, Meridiem, Relative, RelativeTime};
-pub use renderer::{render, RenderedSpec};
+pub use parse_error::Expected;
+pub use parser::{
+    parse, parse_str, parse_str_with_locale, parse_strict, parse_strict_with_locale,
+    parse_with_locale, Absolute, AbsoluteTime, DateTimeSpec, Meridiem, Relative, RelativeTime,
+    TimeTrailer, Zone,
+};
+pub use renderer::{
+    render, render_with_options, AmbiguousTime, DatePreference, NonexistentTime, RenderOptions,
+    RenderedSpec, Renderer,
+};
+pub use span::{parse_located, parse_located_with_locale, Field, FieldSpan};
+pub use timestamp::{
+    format_timestamp, is_in_warning_window, parse_timestamp, parse_timestamp_range, Delay,
+    DelayKind, Interval, Repeater, RepeaterKind, TimeUnit, Timestamp, TimestampKind,
+    TimestampRange, ENGLISH_WEEKDAY_ABBREVIATIONS,
+};
+pub use zoned::{ZonedRenderedSpec, ZonedRenderer};
 
 #[cfg(test)]
 mod test_roundtrip {
@@ -20,165 +61,320 @@ mod test_roundtrip {
 
     #[test]
     fn dashed_date() {
-        let (input, spec) = parse("3-2-5").unwrap();
-        assert_eq!(input, "");
-        let unused: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("3-2-5").unwrap();
+        let unused: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         assert_eq!(
             render(unused, unused, spec).unwrap(),
-            RenderedSpec::Date(NaiveDate::from_ymd(2003, 2, 5))
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2003, 2, 5).unwrap())
         );
     }
 
     #[test]
     fn test_slashed_date() {
-        let (input, spec) = parse("2/5/3").unwrap();
-        assert_eq!(input, "");
-        let unused: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("2/5/3").unwrap();
+        let unused: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         assert_eq!(
             render(unused, unused, spec).unwrap(),
-            RenderedSpec::Date(NaiveDate::from_ymd(2003, 2, 5))
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2003, 2, 5).unwrap())
         );
     }
 
     #[test]
     fn test_day_of_month() {
-        let (input, spec) = parse("14").unwrap();
-        assert_eq!(input, "");
-        let now = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("14").unwrap();
+        let now = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused = now;
         assert_eq!(
             render(unused, now, spec).unwrap(),
-            RenderedSpec::Date(NaiveDate::from_ymd(2006, 6, 14))
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2006, 6, 14).unwrap())
         );
     }
 
     #[test]
     fn test_day_month() {
-        let (input, spec) = parse("12").unwrap();
-        assert_eq!(input, "");
-        let default: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("12").unwrap();
+        let default: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused = default;
         assert_eq!(
             render(default, unused, spec).unwrap(),
-            RenderedSpec::Date(NaiveDate::from_ymd(2006, 7, 12))
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2006, 7, 12).unwrap())
         );
     }
 
     #[test]
     fn test_day_slash_month() {
-        let (input, spec) = parse("2/5").unwrap();
-        assert_eq!(input, "");
-        let default: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("2/5").unwrap();
+        let default: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused = default;
         assert_eq!(
             render(default, unused, spec).unwrap(),
-            RenderedSpec::Date(NaiveDate::from_ymd(2007, 2, 5))
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2007, 2, 5).unwrap())
         );
     }
 
     #[test]
     fn test_week_day() {
-        let (input, spec) = parse("Fri").unwrap();
-        assert_eq!(input, "");
-        let default: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("Fri").unwrap();
+        let default: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused = default;
         assert_eq!(
             render(default, unused, spec).unwrap(),
-            RenderedSpec::Date(NaiveDate::from_ymd(2006, 6, 16))
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2006, 6, 16).unwrap())
         );
     }
 
     #[test]
     fn test_month_day() {
-        let (input, spec) = parse("sep 15").unwrap();
-        assert_eq!(input, "");
-        let default: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("sep 15").unwrap();
+        let default: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused = default;
         assert_eq!(
             render(default, unused, spec).unwrap(),
-            RenderedSpec::Date(NaiveDate::from_ymd(2006, 9, 15))
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2006, 9, 15).unwrap())
         );
 
-        let (input, spec) = parse("feb 15").unwrap();
-        assert_eq!(input, "");
-        let default: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("feb 15").unwrap();
+        let default: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused = default;
         assert_eq!(
             render(default, unused, spec).unwrap(),
-            RenderedSpec::Date(NaiveDate::from_ymd(2007, 2, 15))
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2007, 2, 15).unwrap())
         );
     }
 
     #[test]
     fn test_month_day_year() {
-        let (input, spec) = parse("sep 12 9").unwrap();
-        assert_eq!(input, "");
-        let default: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("sep 12 9").unwrap();
+        let default: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused = default;
         assert_eq!(
             render(default, unused, spec).unwrap(),
-            RenderedSpec::Date(NaiveDate::from_ymd(2009, 9, 12))
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2009, 9, 12).unwrap())
         );
     }
 
     #[test]
     fn test_hour_minute() {
-        let (input, spec) = parse("12:45").unwrap();
-        assert_eq!(input, "");
-        let default: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("12:45").unwrap();
+        let default: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused = default;
         assert_eq!(
             render(default, unused, spec).unwrap(),
-            RenderedSpec::DateTime(NaiveDate::from_ymd(2006, 6, 13).and_hms(12, 45, 0))
+            RenderedSpec::DateTime(
+                NaiveDate::from_ymd_opt(2006, 6, 13)
+                    .unwrap()
+                    .and_hms_opt(12, 45, 0)
+                    .unwrap()
+            )
         );
     }
 
     #[test]
     fn test_day_month_hour_minute() {
-        let (input, spec) = parse("22 sept 0:34").unwrap();
-        assert_eq!(input, "");
-        let default: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("22 sept 0:34").unwrap();
+        let default: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused = default;
         assert_eq!(
             render(default, unused, spec).unwrap(),
-            RenderedSpec::DateTime(NaiveDate::from_ymd(2006, 9, 22).and_hms(0, 34, 0))
+            RenderedSpec::DateTime(
+                NaiveDate::from_ymd_opt(2006, 9, 22)
+                    .unwrap()
+                    .and_hms_opt(0, 34, 0)
+                    .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_date_time() {
+        let spec = parse_strict("2006-06-13 14:00").unwrap();
+        let unused: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        assert_eq!(
+            render(unused, unused, spec).unwrap(),
+            RenderedSpec::DateTime(
+                NaiveDate::from_ymd_opt(2006, 6, 13)
+                    .unwrap()
+                    .and_hms_opt(14, 0, 0)
+                    .unwrap()
+            )
+        );
+
+        let spec = parse_strict("sep 15 9:30").unwrap();
+        let now: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let unused = now;
+        assert_eq!(
+            render(unused, now, spec).unwrap(),
+            RenderedSpec::DateTime(
+                NaiveDate::from_ymd_opt(2006, 9, 15)
+                    .unwrap()
+                    .and_hms_opt(9, 30, 0)
+                    .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_week_day_time() {
+        let spec = parse_strict("fri 3pm").unwrap();
+        let now: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let unused = now;
+        assert_eq!(
+            render(unused, now, spec).unwrap(),
+            RenderedSpec::DateTime(
+                NaiveDate::from_ymd_opt(2006, 6, 16)
+                    .unwrap()
+                    .and_hms_opt(15, 0, 0)
+                    .unwrap()
+            )
+        );
+
+        // Already past 3pm on a Friday: roll over to the next one
+        let spec = parse_strict("fri 3pm").unwrap();
+        let now: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 16)
+            .unwrap()
+            .and_hms_opt(16, 0, 0)
+            .unwrap();
+        let unused = now;
+        assert_eq!(
+            render(unused, now, spec).unwrap(),
+            RenderedSpec::DateTime(
+                NaiveDate::from_ymd_opt(2006, 6, 23)
+                    .unwrap()
+                    .and_hms_opt(15, 0, 0)
+                    .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_date_time_range() {
+        let spec = parse_strict("2006-06-13 11am-1pm").unwrap();
+        let unused: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        assert_eq!(
+            render(unused, unused, spec).unwrap(),
+            RenderedSpec::DateTimeRange(
+                NaiveDate::from_ymd_opt(2006, 6, 13)
+                    .unwrap()
+                    .and_hms_opt(11, 0, 0)
+                    .unwrap(),
+                NaiveDate::from_ymd_opt(2006, 6, 13)
+                    .unwrap()
+                    .and_hms_opt(13, 0, 0)
+                    .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_date_time_duration() {
+        let spec = parse_strict("tue 9:00+1:30").unwrap();
+        let now: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 12)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let unused = now;
+        assert_eq!(
+            render(unused, now, spec).unwrap(),
+            RenderedSpec::DateTimeRange(
+                NaiveDate::from_ymd_opt(2006, 6, 13)
+                    .unwrap()
+                    .and_hms_opt(9, 0, 0)
+                    .unwrap(),
+                NaiveDate::from_ymd_opt(2006, 6, 13)
+                    .unwrap()
+                    .and_hms_opt(10, 30, 0)
+                    .unwrap()
+            )
         );
     }
 
     #[test]
     fn test_week_number() {
-        let (input, spec) = parse("w4").unwrap();
-        assert_eq!(input, "");
-        let unused: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("w4").unwrap();
+        let unused: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         assert_eq!(render(unused, unused, spec).unwrap(), RenderedSpec::Week(4));
     }
 
     #[test]
     fn test_week_date() {
-        let (input, spec) = parse("2012 w4 fri").unwrap();
-        assert_eq!(input, "");
-        let default: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("2012 w4 fri").unwrap();
+        let default: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused = default;
         assert_eq!(
             render(default, unused, spec).unwrap(),
-            RenderedSpec::Date(NaiveDate::from_ymd(2012, 1, 27))
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2012, 1, 27).unwrap())
         );
 
-        let (input, spec) = parse("2012-w04-5").unwrap();
-        assert_eq!(input, "");
-        let default: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("2012-w04-5").unwrap();
+        let default: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused = default;
         assert_eq!(
             render(default, unused, spec).unwrap(),
-            RenderedSpec::Date(NaiveDate::from_ymd(2012, 1, 27))
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2012, 1, 27).unwrap())
         );
     }
 
     #[test]
     fn test_plus_zero() {
-        let (input, spec) = parse("+0").unwrap();
-        assert_eq!(input, "");
-        let default: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("+0").unwrap();
+        let default: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let now: NaiveDateTime = default;
         assert_eq!(
             render(default, now, spec).unwrap(),
@@ -188,9 +384,11 @@ mod test_roundtrip {
 
     #[test]
     fn test_dot() {
-        let (input, spec) = parse(".").unwrap();
-        assert_eq!(input, "");
-        let default: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict(".").unwrap();
+        let default: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let now: NaiveDateTime = default;
         assert_eq!(
             render(default, now, spec).unwrap(),
@@ -200,57 +398,72 @@ mod test_roundtrip {
 
     #[test]
     fn test_plus_hours() {
-        let (input, spec) = parse("+2h").unwrap();
-        assert_eq!(input, "");
-        let now: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("+2h").unwrap();
+        let now: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused: NaiveDateTime = now;
         assert_eq!(
             render(unused, now, spec).unwrap(),
-            RenderedSpec::DateTime(NaiveDate::from_ymd(2006, 6, 13).and_hms(2, 0, 0))
+            RenderedSpec::DateTime(
+                NaiveDate::from_ymd_opt(2006, 6, 13)
+                    .unwrap()
+                    .and_hms_opt(2, 0, 0)
+                    .unwrap()
+            )
         );
     }
 
     #[test]
     fn test_plus_days() {
-        let (input, spec) = parse("+4d").unwrap();
-        assert_eq!(input, "");
-        let now: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("+4d").unwrap();
+        let now: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused: NaiveDateTime = now;
         assert_eq!(
             render(unused, now, spec).unwrap(),
-            RenderedSpec::Date(NaiveDate::from_ymd(2006, 6, 17))
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2006, 6, 17).unwrap())
         );
     }
 
     #[test]
     fn test_plus_weeks() {
-        let (input, spec) = parse("+4w").unwrap();
-        assert_eq!(input, "");
-        let now: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("+4w").unwrap();
+        let now: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused: NaiveDateTime = now;
         assert_eq!(
             render(unused, now, spec).unwrap(),
-            RenderedSpec::Date(NaiveDate::from_ymd(2006, 7, 11))
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2006, 7, 11).unwrap())
         );
     }
 
     #[test]
     fn test_plus_months() {
-        let (input, spec) = parse("+3m").unwrap();
-        assert_eq!(input, "");
-        let now: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("+3m").unwrap();
+        let now: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused: NaiveDateTime = now;
         assert_eq!(
             render(unused, now, spec).unwrap(),
-            RenderedSpec::Date(NaiveDate::from_ymd(2006, 9, 13))
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2006, 9, 13).unwrap())
         );
     }
 
     #[test]
     fn test_minus_months_impossible() {
-        let (input, spec) = parse("-4m").unwrap();
-        assert_eq!(input, "");
-        let now: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 30).and_hms(0, 0, 0);
+        let spec = parse_strict("-4m").unwrap();
+        let now: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 30)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused: NaiveDateTime = now;
         assert!(matches!(
             render(unused, now, spec),
@@ -269,118 +482,316 @@ mod test_roundtrip {
 
     #[test]
     fn test_minus_months() {
-        let (input, spec) = parse("-3m").unwrap();
-        assert_eq!(input, "");
-        let now: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("-3m").unwrap();
+        let now: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused: NaiveDateTime = now;
         assert_eq!(
             render(unused, now, spec).unwrap(),
-            RenderedSpec::Date(NaiveDate::from_ymd(2006, 3, 13))
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2006, 3, 13).unwrap())
         );
     }
 
     #[test]
     fn test_plus_years() {
-        let (input, spec) = parse("+3y").unwrap();
-        assert_eq!(input, "");
-        let now: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("+3y").unwrap();
+        let now: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused: NaiveDateTime = now;
         assert_eq!(
             render(unused, now, spec).unwrap(),
-            RenderedSpec::Date(NaiveDate::from_ymd(2009, 6, 13))
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2009, 6, 13).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_compound_relative() {
+        let spec = parse_strict("+1w3d").unwrap();
+        let now: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let unused: NaiveDateTime = now;
+        assert_eq!(
+            render(unused, now, spec).unwrap(),
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2006, 6, 23).unwrap())
+        );
+
+        let spec = parse_strict("-2y6m").unwrap();
+        assert_eq!(
+            render(unused, now, spec).unwrap(),
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2003, 12, 13).unwrap())
+        );
+
+        let spec = parse_strict("++1m2d").unwrap();
+        let default: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 12, 30)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        assert_eq!(
+            render(default, unused, spec).unwrap(),
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2007, 2, 1).unwrap())
+        );
+
+        let spec = parse_strict("+1d2h").unwrap();
+        assert_eq!(
+            render(unused, now, spec).unwrap(),
+            RenderedSpec::DateTime(
+                NaiveDate::from_ymd_opt(2006, 6, 14)
+                    .unwrap()
+                    .and_hms_opt(2, 0, 0)
+                    .unwrap()
+            )
         );
     }
 
     #[test]
     fn test_default_plus_days() {
-        let (input, spec) = parse("++5").unwrap();
-        assert_eq!(input, "");
-        let default: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("++5").unwrap();
+        let default: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused: NaiveDateTime = default;
         assert_eq!(
             render(default, unused, spec).unwrap(),
-            RenderedSpec::Date(NaiveDate::from_ymd(2006, 6, 18))
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2006, 6, 18).unwrap())
         );
     }
 
     #[test]
     fn test_default_minus_days() {
-        let (input, spec) = parse("--5").unwrap();
-        assert_eq!(input, "");
-        let default: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("--5").unwrap();
+        let default: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused: NaiveDateTime = default;
         assert_eq!(
             render(default, unused, spec).unwrap(),
-            RenderedSpec::Date(NaiveDate::from_ymd(2006, 6, 8))
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2006, 6, 8).unwrap())
         );
     }
 
     #[test]
     fn test_plus_relative_weekdays() {
-        let (input, spec) = parse("+2tue").unwrap();
-        assert_eq!(input, "");
-        let now: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("+2tue").unwrap();
+        let now: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused: NaiveDateTime = now;
         assert_eq!(
             render(unused, now, spec).unwrap(),
-            RenderedSpec::Date(NaiveDate::from_ymd(2006, 6, 27))
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2006, 6, 27).unwrap())
         );
     }
 
     #[test]
     fn test_minus_relative_weekdays() {
-        let (input, spec) = parse("-wed").unwrap();
-        assert_eq!(input, "");
-        let now: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("-wed").unwrap();
+        let now: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused: NaiveDateTime = now;
         assert_eq!(
             render(unused, now, spec).unwrap(),
-            RenderedSpec::Date(NaiveDate::from_ymd(2006, 6, 7))
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2006, 6, 7).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_keywords() {
+        let now: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let unused: NaiveDateTime = now;
+
+        let spec = parse_strict("tomorrow").unwrap();
+        assert_eq!(
+            render(unused, now, spec).unwrap(),
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2006, 6, 14).unwrap())
+        );
+
+        let spec = parse_strict("yesterday").unwrap();
+        assert_eq!(
+            render(unused, now, spec).unwrap(),
+            RenderedSpec::Date(NaiveDate::from_ymd_opt(2006, 6, 12).unwrap())
+        );
+
+        let spec = parse_strict("2006-06-20 noon").unwrap();
+        assert_eq!(
+            render(unused, now, spec).unwrap(),
+            RenderedSpec::DateTime(
+                NaiveDate::from_ymd_opt(2006, 6, 20)
+                    .unwrap()
+                    .and_hms_opt(12, 0, 0)
+                    .unwrap()
+            )
         );
     }
 
     #[test]
     fn test_time_range() {
-        let (input, spec) = parse("11am-1:15pm").unwrap();
-        assert_eq!(input, "");
-        let now: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("11am-1:15pm").unwrap();
+        let now: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused: NaiveDateTime = now;
         assert_eq!(
             render(unused, now, spec).unwrap(),
             RenderedSpec::TimeRange(
-                NaiveTime::from_hms(11, 0, 0),
-                NaiveTime::from_hms(13, 15, 0)
+                NaiveTime::from_hms_opt(11, 0, 0).unwrap(),
+                NaiveTime::from_hms_opt(13, 15, 0).unwrap()
             )
         );
     }
 
     #[test]
     fn test_time_range_double_dash() {
-        let (input, spec) = parse("11am--1:15pm").unwrap();
-        assert_eq!(input, "");
-        let now: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("11am--1:15pm").unwrap();
+        let now: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused: NaiveDateTime = now;
         assert_eq!(
             render(unused, now, spec).unwrap(),
             RenderedSpec::TimeRange(
-                NaiveTime::from_hms(11, 0, 0),
-                NaiveTime::from_hms(13, 15, 0)
+                NaiveTime::from_hms_opt(11, 0, 0).unwrap(),
+                NaiveTime::from_hms_opt(13, 15, 0).unwrap()
             )
         );
     }
 
     #[test]
     fn test_time_duration() {
-        let (input, spec) = parse("11am+2:15").unwrap();
-        assert_eq!(input, "");
-        let now: NaiveDateTime = NaiveDate::from_ymd(2006, 6, 13).and_hms(0, 0, 0);
+        let spec = parse_strict("11am+2:15").unwrap();
+        let now: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
         let unused: NaiveDateTime = now;
         assert_eq!(
             render(unused, now, spec).unwrap(),
             RenderedSpec::TimeRange(
-                NaiveTime::from_hms(11, 0, 0),
-                NaiveTime::from_hms(13, 15, 0)
+                NaiveTime::from_hms_opt(11, 0, 0).unwrap(),
+                NaiveTime::from_hms_opt(13, 15, 0).unwrap()
             )
         );
     }
+
+    #[test]
+    fn test_out_of_range() {
+        let spec = parse_strict("2006-13-45").unwrap();
+        let now: NaiveDateTime = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let unused: NaiveDateTime = now;
+        assert!(matches!(
+            render(unused, now, spec),
+            Err(OrgModeDateTimeError::FieldOutOfRange {
+                field: Field::Month,
+                value: 13
+            })
+        ));
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod test_serde {
+    use chrono::NaiveDate;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_spec() {
+        let spec = parse_strict("sep 15 3pm").unwrap();
+        let value = serde_json::to_value(&spec).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "kind": "absolute",
+                "value": {
+                    "year": null,
+                    "month": 9,
+                    "day": 15,
+                    "week": null,
+                    "weekday": null,
+                    "hour": 3,
+                    "minute": null,
+                    "meridiem": "pm",
+                    "zone": null,
+                }
+            })
+        );
+        assert_eq!(serde_json::from_value::<DateTimeSpec>(value).unwrap(), spec);
+    }
+
+    #[test]
+    fn test_tuple_variant() {
+        let spec = parse_strict("9:00+1:30").unwrap();
+        let value = serde_json::to_value(&spec).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "kind": "time_range_absolute_start_relative_end",
+                "value": [
+                    {"hour": 9, "minute": 0, "meridiem": null, "zone": null},
+                    {"hours": 1, "minutes": 30},
+                ]
+            })
+        );
+        assert_eq!(serde_json::from_value::<DateTimeSpec>(value).unwrap(), spec);
+    }
+
+    #[test]
+    fn test_zone() {
+        let offset = serde_json::to_value(Zone::Offset(7200)).unwrap();
+        assert_eq!(offset, json!({"kind": "offset", "value": 7200}));
+        assert_eq!(
+            serde_json::from_value::<Zone>(offset).unwrap(),
+            Zone::Offset(7200)
+        );
+        let named = serde_json::to_value(Zone::Named(chrono_tz::Europe::Warsaw)).unwrap();
+        assert_eq!(named, json!({"kind": "named", "value": "Europe/Warsaw"}));
+        assert_eq!(
+            serde_json::from_value::<Zone>(named).unwrap(),
+            Zone::Named(chrono_tz::Europe::Warsaw)
+        );
+    }
+
+    #[test]
+    fn test_rendered() {
+        let start = NaiveDate::from_ymd_opt(2006, 6, 13)
+            .unwrap()
+            .and_hms_opt(11, 0, 0)
+            .unwrap();
+        let rendered = RenderedSpec::DateTimeRange(start, start + chrono::Duration::hours(2));
+        let value = serde_json::to_value(&rendered).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "kind": "date_time_range",
+                "value": ["2006-06-13T11:00:00", "2006-06-13T13:00:00"]
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<RenderedSpec>(value).unwrap(),
+            rendered
+        );
+        assert_eq!(
+            serde_json::to_value(RenderedSpec::Date(start.date())).unwrap(),
+            json!({"kind": "date", "value": "2006-06-13"})
+        );
+    }
 }
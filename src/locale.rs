@@ -0,0 +1,478 @@
+use chrono::{Month, Weekday};
+
+const MONTHS: [Month; 12] = [
+    Month::January,
+    Month::February,
+    Month::March,
+    Month::April,
+    Month::May,
+    Month::June,
+    Month::July,
+    Month::August,
+    Month::September,
+    Month::October,
+    Month::November,
+    Month::December,
+];
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+// Shortest prefix of a full name that is accepted on its own ("sep" for "september")
+const MIN_PREFIX_LENGTH: usize = 3;
+
+// Month and weekday names the parser recognizes, like Emacs' `parse-time-months` and
+// `parse-time-weekdays`.  Each month and weekday has a full name and an abbreviation.  Besides
+// those, any prefix of the full name at least three characters long is accepted as long as no
+// other month (or weekday) starts with it.  Matching is case-insensitive.
+#[derive(Debug, Clone)]
+pub struct Locale {
+    // Lowercased
+    month_names: Vec<(String, Month)>,
+    // Lowercased
+    weekday_names: Vec<(String, Weekday)>,
+    // As written in timestamps, Monday first
+    weekday_abbreviations: [String; 7],
+    // Lowercased full names, abbreviations and added names, offered as completions
+    month_vocabulary: Vec<String>,
+    weekday_vocabulary: Vec<String>,
+}
+
+fn accepted_names<T: Copy>(entries: &[(&str, &str)], values: &[T]) -> Vec<(String, T)> {
+    let full_names: Vec<String> = entries
+        .iter()
+        .map(|(name, _)| name.to_lowercase())
+        .collect();
+    let mut result = Vec::new();
+    for (index, ((_, abbreviation), value)) in entries.iter().zip(values).enumerate() {
+        let full_name = &full_names[index];
+        result.push((full_name.clone(), *value));
+        result.push((abbreviation.to_lowercase(), *value));
+        for (length, (end, _)) in full_name.char_indices().enumerate() {
+            let prefix = &full_name[..end];
+            let ambiguous = full_names
+                .iter()
+                .enumerate()
+                .any(|(other, name)| other != index && name.starts_with(prefix));
+            if length >= MIN_PREFIX_LENGTH && !ambiguous {
+                result.push((prefix.to_string(), *value));
+            }
+        }
+    }
+    result
+}
+
+fn vocabulary(entries: &[(&str, &str)]) -> Vec<String> {
+    let mut result: Vec<String> = Vec::new();
+    for (name, abbreviation) in entries {
+        for word in [name.to_lowercase(), abbreviation.to_lowercase()] {
+            if !result.contains(&word) {
+                result.push(word);
+            }
+        }
+    }
+    result
+}
+
+fn completions<'a>(vocabulary: &'a [String], prefix: &str) -> Vec<&'a str> {
+    let prefix = prefix.to_lowercase();
+    vocabulary
+        .iter()
+        .filter(|word| word.starts_with(&prefix))
+        .map(String::as_str)
+        .collect()
+}
+
+// Length in bytes of the prefix of `input` that matches the (lowercase) `name`
+fn match_length(input: &str, name: &str) -> Option<usize> {
+    let mut input_chars = input.char_indices();
+    for expected in name.chars() {
+        let (_, actual) = input_chars.next()?;
+        if !actual.to_lowercase().eq(expected.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(input_chars.next().map_or(input.len(), |(end, _)| end))
+}
+
+// The longest name `input` starts with
+fn longest_match<T: Copy>(names: &[(String, T)], input: &str) -> Option<(usize, T)> {
+    names
+        .iter()
+        .filter_map(|(name, value)| Some((match_length(input, name)?, *value)))
+        .max_by_key(|(length, _)| *length)
+}
+
+// Edit distance counting insertions, deletions, substitutions and swaps of adjacent characters
+// (optimal string alignment), so that "thrusday" is a single typo away from "thursday"
+fn edit_distance(left: &str, right: &str) -> usize {
+    let left: Vec<char> = left.chars().collect();
+    let right: Vec<char> = right.chars().collect();
+    let mut distances = vec![vec![0; right.len() + 1]; left.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+    for i in 1..=left.len() {
+        for j in 1..=right.len() {
+            let cost = usize::from(left[i - 1] != right[j - 1]);
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && left[i - 1] == right[j - 2] && left[i - 2] == right[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+    distances[left.len()][right.len()]
+}
+
+// The closest accepted name of each value within a third of `word`'s length (but at least
+// one) typos away, as (distance, name) pairs, closest first
+fn closest_names<'a, T: Copy + PartialEq>(
+    names: &'a [(String, T)],
+    word: &str,
+) -> Vec<(usize, &'a str)> {
+    let word = word.to_lowercase();
+    let max_distance = (word.chars().count() / 3).max(1);
+    let mut best: Vec<(usize, &str, T)> = Vec::new();
+    for (name, value) in names {
+        let distance = edit_distance(&word, name);
+        if distance > max_distance {
+            continue;
+        }
+        match best.iter_mut().find(|(_, _, other)| other == value) {
+            Some(entry) if distance < entry.0 => *entry = (distance, name, *value),
+            Some(_) => {}
+            None => best.push((distance, name, *value)),
+        }
+    }
+    best.sort_by_key(|(distance, _, _)| *distance);
+    best.into_iter()
+        .map(|(distance, name, _)| (distance, name))
+        .collect()
+}
+
+impl Locale {
+    // `months` and `weekdays` hold (full name, abbreviation) pairs, January and Monday first
+    pub fn new(months: [(&str, &str); 12], weekdays: [(&str, &str); 7]) -> Locale {
+        Locale {
+            month_names: accepted_names(&months, &MONTHS),
+            weekday_names: accepted_names(&weekdays, &WEEKDAYS),
+            weekday_abbreviations: weekdays.map(|(_, abbreviation)| abbreviation.to_string()),
+            month_vocabulary: vocabulary(&months),
+            weekday_vocabulary: vocabulary(&weekdays),
+        }
+    }
+
+    pub fn english() -> Locale {
+        Locale::new(
+            [
+                ("january", "jan"),
+                ("february", "feb"),
+                ("march", "mar"),
+                ("april", "apr"),
+                ("may", "may"),
+                ("june", "jun"),
+                ("july", "jul"),
+                ("august", "aug"),
+                ("september", "sep"),
+                ("october", "oct"),
+                ("november", "nov"),
+                ("december", "dec"),
+            ],
+            [
+                ("monday", "Mon"),
+                ("tuesday", "Tue"),
+                ("wednesday", "Wed"),
+                ("thursday", "Thu"),
+                ("friday", "Fri"),
+                ("saturday", "Sat"),
+                ("sunday", "Sun"),
+            ],
+        )
+    }
+
+    pub fn german() -> Locale {
+        Locale::new(
+            [
+                ("januar", "jan"),
+                ("februar", "feb"),
+                ("märz", "mär"),
+                ("april", "apr"),
+                ("mai", "mai"),
+                ("juni", "jun"),
+                ("juli", "jul"),
+                ("august", "aug"),
+                ("september", "sep"),
+                ("oktober", "okt"),
+                ("november", "nov"),
+                ("dezember", "dez"),
+            ],
+            [
+                ("montag", "Mo"),
+                ("dienstag", "Di"),
+                ("mittwoch", "Mi"),
+                ("donnerstag", "Do"),
+                ("freitag", "Fr"),
+                ("samstag", "Sa"),
+                ("sonntag", "So"),
+            ],
+        )
+    }
+
+    pub fn french() -> Locale {
+        Locale::new(
+            [
+                ("janvier", "janv"),
+                ("février", "févr"),
+                ("mars", "mars"),
+                ("avril", "avr"),
+                ("mai", "mai"),
+                ("juin", "juin"),
+                ("juillet", "juil"),
+                ("août", "août"),
+                ("septembre", "sept"),
+                ("octobre", "oct"),
+                ("novembre", "nov"),
+                ("décembre", "déc"),
+            ],
+            [
+                ("lundi", "lun"),
+                ("mardi", "mar"),
+                ("mercredi", "mer"),
+                ("jeudi", "jeu"),
+                ("vendredi", "ven"),
+                ("samedi", "sam"),
+                ("dimanche", "dim"),
+            ],
+        )
+    }
+
+    pub fn polish() -> Locale {
+        Locale::new(
+            [
+                ("styczeń", "sty"),
+                ("luty", "lut"),
+                ("marzec", "mar"),
+                ("kwiecień", "kwi"),
+                ("maj", "maj"),
+                ("czerwiec", "cze"),
+                ("lipiec", "lip"),
+                ("sierpień", "sie"),
+                ("wrzesień", "wrz"),
+                ("październik", "paź"),
+                ("listopad", "lis"),
+                ("grudzień", "gru"),
+            ],
+            [
+                ("poniedziałek", "pon"),
+                ("wtorek", "wto"),
+                ("środa", "śro"),
+                ("czwartek", "czw"),
+                ("piątek", "pią"),
+                ("sobota", "sob"),
+                ("niedziela", "nie"),
+            ],
+        )
+    }
+
+    pub fn spanish() -> Locale {
+        Locale::new(
+            [
+                ("enero", "ene"),
+                ("febrero", "feb"),
+                ("marzo", "mar"),
+                ("abril", "abr"),
+                ("mayo", "may"),
+                ("junio", "jun"),
+                ("julio", "jul"),
+                ("agosto", "ago"),
+                ("septiembre", "sep"),
+                ("octubre", "oct"),
+                ("noviembre", "nov"),
+                ("diciembre", "dic"),
+            ],
+            [
+                ("lunes", "lun"),
+                ("martes", "mar"),
+                ("miércoles", "mié"),
+                ("jueves", "jue"),
+                ("viernes", "vie"),
+                ("sábado", "sáb"),
+                ("domingo", "dom"),
+            ],
+        )
+    }
+
+    // One of the built-in tables by its ISO 639-1 code ("en", "de", "fr", "pl", "es")
+    pub fn from_code(code: &str) -> Option<Locale> {
+        match code {
+            "en" => Some(Locale::english()),
+            "de" => Some(Locale::german()),
+            "fr" => Some(Locale::french()),
+            "pl" => Some(Locale::polish()),
+            "es" => Some(Locale::spanish()),
+            _ => None,
+        }
+    }
+
+    // Registers an additional spelling, e.g. "sept" or "Sept." for September.  Unlike full
+    // names, it's only accepted verbatim (modulo case), not abbreviated any further.
+    pub fn add_month_name(&mut self, month: Month, name: &str) {
+        self.month_names.push((name.to_lowercase(), month));
+        self.month_vocabulary.push(name.to_lowercase());
+    }
+
+    pub fn add_weekday_name(&mut self, weekday: Weekday, name: &str) {
+        self.weekday_names.push((name.to_lowercase(), weekday));
+        self.weekday_vocabulary.push(name.to_lowercase());
+    }
+
+    // Suitable for `format_timestamp()`
+    pub fn weekday_abbreviations(&self) -> [&str; 7] {
+        [0, 1, 2, 3, 4, 5, 6].map(|index| self.weekday_abbreviations[index].as_str())
+    }
+
+    // Returns the month `input` starts with and the length of its name in bytes
+    pub fn match_month(&self, input: &str) -> Option<(usize, Month)> {
+        longest_match(&self.month_names, input)
+    }
+
+    // Returns the weekday `input` starts with and the length of its name in bytes
+    pub fn match_weekday(&self, input: &str) -> Option<(usize, Weekday)> {
+        longest_match(&self.weekday_names, input)
+    }
+
+    // Full names, abbreviations and added names of months starting with `prefix`
+    pub fn complete_month(&self, prefix: &str) -> Vec<&str> {
+        completions(&self.month_vocabulary, prefix)
+    }
+
+    // Full names, abbreviations and added names of weekdays starting with `prefix`
+    pub fn complete_weekday(&self, prefix: &str) -> Vec<&str> {
+        completions(&self.weekday_vocabulary, prefix)
+    }
+
+    // Month names `word` may be a misspelling of, as (edit distance, name) pairs, closest first
+    pub fn suggest_month(&self, word: &str) -> Vec<(usize, &str)> {
+        closest_names(&self.month_names, word)
+    }
+
+    // Weekday names `word` may be a misspelling of, as (edit distance, name) pairs, closest first
+    pub fn suggest_weekday(&self, word: &str) -> Vec<(usize, &str)> {
+        closest_names(&self.weekday_names, word)
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Locale::english()
+    }
+}
+
+#[cfg(test)]
+mod test_locale {
+    use super::*;
+
+    #[test]
+    fn test_english_prefixes() {
+        let locale = Locale::english();
+        assert_eq!(locale.match_month("sep"), Some((3, Month::September)));
+        assert_eq!(locale.match_month("Septem"), Some((6, Month::September)));
+        assert_eq!(
+            locale.match_month("september 15"),
+            Some((9, Month::September))
+        );
+        assert_eq!(locale.match_month("se"), None);
+        assert_eq!(locale.match_weekday("thurs"), Some((5, Weekday::Thu)));
+    }
+
+    #[test]
+    fn test_ambiguous_prefix() {
+        let locale = Locale::french();
+        // "jui" begins both "juin" and "juillet"
+        assert_eq!(locale.match_month("jui"), None);
+        assert_eq!(locale.match_month("juil"), Some((4, Month::July)));
+        assert_eq!(locale.match_month("juin"), Some((4, Month::June)));
+    }
+
+    #[test]
+    fn test_non_ascii() {
+        let locale = Locale::polish();
+        assert_eq!(
+            locale.match_month("PAŹDZIERNIK"),
+            Some(("październik".len(), Month::October))
+        );
+        assert_eq!(
+            locale.match_weekday("śr"),
+            None,
+            "too short to be an abbreviation"
+        );
+        assert_eq!(
+            locale.match_weekday("środa"),
+            Some(("środa".len(), Weekday::Wed))
+        );
+        let locale = Locale::german();
+        assert_eq!(locale.match_weekday("Di"), Some((2, Weekday::Tue)));
+        assert_eq!(
+            locale.match_month("märz"),
+            Some(("märz".len(), Month::March))
+        );
+    }
+
+    #[test]
+    fn test_custom_names() {
+        let mut locale = Locale::english();
+        assert_eq!(locale.match_month("sept."), Some((4, Month::September)));
+        locale.add_month_name(Month::September, "Sept.");
+        assert_eq!(locale.match_month("sept."), Some((5, Month::September)));
+    }
+
+    #[test]
+    fn test_weekday_abbreviations() {
+        assert_eq!(
+            Locale::german().weekday_abbreviations(),
+            ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"]
+        );
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("sep", "sep"), 0);
+        assert_eq!(edit_distance("setp", "sep"), 1);
+        assert_eq!(edit_distance("thrusday", "thursday"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "may"), 3);
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let locale = Locale::english();
+        assert_eq!(locale.suggest_month("setp"), vec![(1, "sep")]);
+        assert_eq!(locale.suggest_weekday("Thrusday"), vec![(1, "thursday")]);
+        assert_eq!(locale.suggest_month("xyzzy"), vec![]);
+    }
+
+    #[test]
+    fn test_completions() {
+        let locale = Locale::english();
+        assert_eq!(locale.complete_month("Se"), vec!["september", "sep"]);
+        assert_eq!(locale.complete_month("may"), vec!["may"]);
+        assert_eq!(
+            locale.complete_weekday("t"),
+            vec!["tuesday", "tue", "thursday", "thu"]
+        );
+        assert_eq!(locale.complete_weekday("x"), Vec::<&str>::new());
+    }
+}
@@ -0,0 +1,215 @@
+use std::cell::RefCell;
+use std::fmt;
+
+use nom::error::{ContextError, ErrorKind, ParseError};
+use nom::IResult;
+
+use crate::error::OrgModeDateTimeError;
+
+// What the parser was looking for at the point where the input stopped matching
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Expected {
+    Token(&'static str),
+    Digit,
+    Whitespace,
+    MonthName,
+    WeekdayName,
+    WeekNumber,
+    // Digits too many to be any field
+    SmallerNumber,
+    Zone,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Token(token) => write!(f, "`{}`", token),
+            Expected::Digit => write!(f, "digit"),
+            Expected::Whitespace => write!(f, "whitespace"),
+            Expected::MonthName => write!(f, "month name"),
+            Expected::WeekdayName => write!(f, "weekday name"),
+            Expected::WeekNumber => write!(f, "week number"),
+            Expected::SmallerNumber => write!(f, "smaller number"),
+            Expected::Zone => write!(f, "time zone"),
+        }
+    }
+}
+
+pub(crate) fn describe_expected(expected: &[Expected]) -> String {
+    match expected {
+        [] => "nothing".to_string(),
+        [only] => only.to_string(),
+        [init @ .., last] => {
+            let init: Vec<String> = init.iter().map(Expected::to_string).collect();
+            format!("{} or {}", init.join(", "), last)
+        }
+    }
+}
+
+pub(crate) fn describe_suggestions(suggestions: &[String]) -> String {
+    if suggestions.is_empty() {
+        return String::new();
+    }
+    let quoted: Vec<String> = suggestions
+        .iter()
+        .map(|suggestion| format!("`{}`", suggestion))
+        .collect();
+    format!("; did you mean {}?", quoted.join(" or "))
+}
+
+thread_local! {
+    // Every expectation that wasn't met, with the length of the input left at that point, while
+    // record_expectations() runs.  Unlike ParseFailure, this keeps the ones opt() and a
+    // successful alt() throw away.
+    static EXPECTATIONS: RefCell<Option<Vec<(usize, Expected)>>> = const { RefCell::new(None) };
+}
+
+fn note_expectations(input: &str, expected: &[Expected]) {
+    EXPECTATIONS.with(|expectations| {
+        if let Some(expectations) = expectations.borrow_mut().as_mut() {
+            for item in expected {
+                expectations.push((input.len(), item.clone()));
+            }
+        }
+    });
+}
+
+pub(crate) fn record_expectations<T>(f: impl FnOnce() -> T) -> (T, Vec<(usize, Expected)>) {
+    EXPECTATIONS.with(|expectations| *expectations.borrow_mut() = Some(Vec::new()));
+    let result = f();
+    let expectations = EXPECTATIONS.with(|expectations| expectations.borrow_mut().take());
+    (result, expectations.unwrap_or_default())
+}
+
+// A nom error that remembers the failure that got furthest into the input.
+// Alternatives failing at the same position pool what they expected and which
+// top-level productions (attached with nom's `context`) got that far.
+#[derive(Debug, PartialEq)]
+pub(crate) struct ParseFailure<'a> {
+    pub(crate) input: &'a str,
+    pub(crate) kind: ErrorKind,
+    pub(crate) expected: Vec<Expected>,
+    pub(crate) productions: Vec<&'static str>,
+}
+
+pub(crate) type PResult<'a, T> = IResult<&'a str, T, ParseFailure<'a>>;
+
+fn push_unique<T: PartialEq>(items: &mut Vec<T>, item: T) {
+    if !items.contains(&item) {
+        items.push(item);
+    }
+}
+
+impl<'a> ParseFailure<'a> {
+    pub(crate) fn expecting(input: &'a str, expected: Expected) -> Self {
+        note_expectations(input, std::slice::from_ref(&expected));
+        ParseFailure {
+            input,
+            kind: ErrorKind::Tag,
+            expected: vec![expected],
+            productions: Vec::new(),
+        }
+    }
+
+    pub(crate) fn into_error(
+        self,
+        original: &str,
+        suggest: impl FnOnce(&str, &[Expected]) -> Vec<String>,
+    ) -> OrgModeDateTimeError {
+        let suggestions = suggest(self.input, &self.expected);
+        OrgModeDateTimeError::UnexpectedInput {
+            offset: original.len() - self.input.len(),
+            expected: self.expected,
+            productions: self.productions,
+            suggestions,
+        }
+    }
+}
+
+impl<'a> ParseError<&'a str> for ParseFailure<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        let expected = match kind {
+            ErrorKind::Digit => vec![Expected::Digit],
+            ErrorKind::Space | ErrorKind::MultiSpace => vec![Expected::Whitespace],
+            _ => Vec::new(),
+        };
+        note_expectations(input, &expected);
+        ParseFailure {
+            input,
+            kind,
+            expected,
+            productions: Vec::new(),
+        }
+    }
+
+    fn append(_input: &'a str, _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn or(mut self, other: Self) -> Self {
+        // Less input left means the alternative got further
+        if other.input.len() < self.input.len() {
+            return other;
+        }
+        if self.input.len() < other.input.len() {
+            return self;
+        }
+        for expected in other.expected {
+            push_unique(&mut self.expected, expected);
+        }
+        for production in other.productions {
+            push_unique(&mut self.productions, production);
+        }
+        self
+    }
+}
+
+impl<'a> ContextError<&'a str> for ParseFailure<'a> {
+    fn add_context(_input: &'a str, production: &'static str, mut other: Self) -> Self {
+        push_unique(&mut other.productions, production);
+        other
+    }
+}
+
+impl<'a> From<ParseFailure<'a>> for nom::error::Error<&'a str> {
+    fn from(failure: ParseFailure<'a>) -> Self {
+        nom::error::Error::new(failure.input, failure.kind)
+    }
+}
+
+// `suggest` gets the input left at the failure and what was expected there
+pub(crate) fn into_error(
+    original: &str,
+    err: nom::Err<ParseFailure>,
+    suggest: impl FnOnce(&str, &[Expected]) -> Vec<String>,
+) -> OrgModeDateTimeError {
+    match err {
+        nom::Err::Error(failure) | nom::Err::Failure(failure) => {
+            failure.into_error(original, suggest)
+        }
+        // The grammar only uses complete parsers, so running out of input is a plain mismatch
+        nom::Err::Incomplete(_) => OrgModeDateTimeError::UnexpectedInput {
+            offset: original.len(),
+            expected: Vec::new(),
+            productions: Vec::new(),
+            suggestions: Vec::new(),
+        },
+    }
+}
+
+// nom's tag, but reporting the token it was looking for
+pub(crate) fn tag<'a>(token: &'static str) -> impl Fn(&'a str) -> PResult<'a, &'a str> {
+    move |input| {
+        nom::bytes::complete::tag(token)(input).map_err(|_: nom::Err<ParseFailure>| {
+            nom::Err::Error(ParseFailure::expecting(input, Expected::Token(token)))
+        })
+    }
+}
+
+pub(crate) fn tag_no_case<'a>(token: &'static str) -> impl Fn(&'a str) -> PResult<'a, &'a str> {
+    move |input| {
+        nom::bytes::complete::tag_no_case(token)(input).map_err(|_: nom::Err<ParseFailure>| {
+            nom::Err::Error(ParseFailure::expecting(input, Expected::Token(token)))
+        })
+    }
+}
@@ -1,16 +1,48 @@
+use std::sync::OnceLock;
+
 use chrono::{Month, Weekday};
-use nom::bytes::complete::{tag, tag_no_case};
-use nom::character::complete::{digit1, space1};
-use nom::combinator::opt;
+use chrono_tz::Tz;
+use nom::bytes::complete::{take_while, take_while_m_n};
+use nom::character::complete::{digit1, satisfy, space0, space1};
+use nom::combinator::{map, not, opt, recognize, verify};
+use nom::error::{ContextError, ErrorKind, ParseError};
+use nom::sequence::{pair, preceded};
 use nom::{branch::alt, IResult};
-
-#[derive(Debug, PartialEq, Eq)]
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::{OrgModeDateTimeError, Result};
+use crate::locale::Locale;
+use crate::parse_error::{into_error, tag, tag_no_case, Expected, PResult, ParseFailure};
+use crate::span::{field, fields, range_end, relabel_from, Field};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "lowercase")
+)]
 pub enum Meridiem {
     AM,
     PM,
 }
 
-#[derive(Debug, PartialEq, Eq, Default)]
+// A time zone written after a time
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(tag = "kind", content = "value", rename_all = "snake_case")
+)]
+pub enum Zone {
+    // Seconds east of UTC: "+02:00", "-0530", "Z"
+    Offset(i32),
+    // An IANA time zone: "Europe/Warsaw", "UTC"
+    Named(Tz),
+}
+
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Absolute {
     pub year: Option<u32>,
     pub month: Option<u32>,
@@ -20,16 +52,21 @@ pub struct Absolute {
     pub hour: Option<u32>,
     pub minute: Option<u32>,
     pub meridiem: Option<Meridiem>,
+    pub zone: Option<Zone>,
 }
 
-#[derive(Debug, PartialEq, Eq, Default)]
+// Starting a time range, the zone applies to the whole range
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AbsoluteTime {
     pub hour: Option<u32>,
     pub minute: Option<u32>,
     pub meridiem: Option<Meridiem>,
+    pub zone: Option<Zone>,
 }
 
-#[derive(Debug, PartialEq, Eq, Default)]
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Relative {
     pub hours: Option<u32>,
     pub days: Option<u32>,
@@ -39,253 +76,195 @@ pub struct Relative {
     pub years: Option<u32>,
 }
 
-#[derive(Debug, PartialEq, Eq, Default)]
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RelativeTime {
     pub hours: Option<u32>,
     pub minutes: Option<u32>,
 }
 
 // https://orgmode.org/manual/The-date_002ftime-prompt.html
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(tag = "kind", content = "value", rename_all = "snake_case")
+)]
 pub enum DateTimeSpec {
     Absolute(Absolute),
     NowRelativeFuture(Relative),
     NowRelativePast(Relative),
+    // "tomorrow 3pm", "+2d 9:00-10:00"
+    NowRelativeFutureWithTime(Relative, TimeTrailer),
+    NowRelativePastWithTime(Relative, TimeTrailer),
     DefaultRelativeFuture(Relative),
     DefaultRelativePast(Relative),
     TimeRangeAbsoluteStartAbsoluteEnd(AbsoluteTime, AbsoluteTime),
     TimeRangeAbsoluteStartRelativeEnd(AbsoluteTime, RelativeTime),
+    DateTimeRangeAbsoluteStartAbsoluteEnd(Absolute, AbsoluteTime),
+    DateTimeRangeAbsoluteStartRelativeEnd(Absolute, RelativeTime),
+}
+
+// What may follow a date: a time, or a time range
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(tag = "kind", content = "value", rename_all = "snake_case")
+)]
+pub enum TimeTrailer {
+    Time(AbsoluteTime),
+    RangeAbsoluteEnd(AbsoluteTime, AbsoluteTime),
+    RangeRelativeEnd(AbsoluteTime, RelativeTime),
+}
+
+fn month(locale: &Locale) -> impl Fn(&str) -> PResult<'_, Month> + '_ {
+    move |input| match locale.match_month(input) {
+        Some((length, month)) => Ok((&input[length..], month)),
+        None => Err(nom::Err::Error(ParseFailure::expecting(
+            input,
+            Expected::MonthName,
+        ))),
+    }
 }
 
-fn monday(input: &str) -> IResult<&str, Weekday> {
-    let (input, _) = alt((
-        tag_no_case("monday"),
-        tag_no_case("monda"),
-        tag_no_case("mond"),
-        tag_no_case("mon"),
-    ))(input)?;
-    Ok((input, Weekday::Mon))
-}
-
-fn tuesday(input: &str) -> IResult<&str, Weekday> {
-    let (input, _) = alt((
-        tag_no_case("tuesday"),
-        tag_no_case("tuesda"),
-        tag_no_case("tuesd"),
-        tag_no_case("tue"),
-    ))(input)?;
-    Ok((input, Weekday::Tue))
-}
-
-fn wednesday(input: &str) -> IResult<&str, Weekday> {
-    let (input, _) = alt((
-        tag_no_case("wednesday"),
-        tag_no_case("wednesda"),
-        tag_no_case("wednesd"),
-        tag_no_case("wednes"),
-        tag_no_case("wedne"),
-        tag_no_case("wedn"),
-        tag_no_case("wed"),
-    ))(input)?;
-    Ok((input, Weekday::Wed))
-}
-
-fn thursday(input: &str) -> IResult<&str, Weekday> {
-    let (input, _) = alt((
-        tag_no_case("thursday"),
-        tag_no_case("thursda"),
-        tag_no_case("thursd"),
-        tag_no_case("thurs"),
-        tag_no_case("thur"),
-        tag_no_case("thu"),
-    ))(input)?;
-    Ok((input, Weekday::Thu))
-}
-
-fn friday(input: &str) -> IResult<&str, Weekday> {
-    let (input, _) = alt((
-        tag_no_case("friday"),
-        tag_no_case("frida"),
-        tag_no_case("frid"),
-        tag_no_case("fri"),
-    ))(input)?;
-    Ok((input, Weekday::Fri))
-}
-
-fn saturday(input: &str) -> IResult<&str, Weekday> {
-    let (input, _) = alt((
-        tag_no_case("saturday"),
-        tag_no_case("saturda"),
-        tag_no_case("saturd"),
-        tag_no_case("satur"),
-        tag_no_case("satu"),
-        tag_no_case("sat"),
-    ))(input)?;
-    Ok((input, Weekday::Sat))
-}
-
-fn sunday(input: &str) -> IResult<&str, Weekday> {
-    let (input, _) = alt((
-        tag_no_case("sunday"),
-        tag_no_case("sunda"),
-        tag_no_case("sund"),
-        tag_no_case("sun"),
-    ))(input)?;
-    Ok((input, Weekday::Sun))
-}
-
-fn january(input: &str) -> IResult<&str, Month> {
-    let (input, _) = alt((
-        tag_no_case("january"),
-        tag_no_case("januar"),
-        tag_no_case("janu"),
-        tag_no_case("jan"),
-    ))(input)?;
-    Ok((input, Month::January))
-}
-
-fn february(input: &str) -> IResult<&str, Month> {
-    let (input, _) = alt((
-        tag_no_case("february"),
-        tag_no_case("februar"),
-        tag_no_case("februa"),
-        tag_no_case("febru"),
-        tag_no_case("febr"),
-        tag_no_case("feb"),
-    ))(input)?;
-    Ok((input, Month::February))
-}
-
-fn march(input: &str) -> IResult<&str, Month> {
-    let (input, _) = alt((
-        tag_no_case("march"),
-        tag_no_case("marc"),
-        tag_no_case("mar"),
-    ))(input)?;
-    Ok((input, Month::March))
-}
-
-fn april(input: &str) -> IResult<&str, Month> {
-    let (input, _) = alt((
-        tag_no_case("april"),
-        tag_no_case("apri"),
-        tag_no_case("apr"),
-    ))(input)?;
-    Ok((input, Month::April))
-}
-
-fn may(input: &str) -> IResult<&str, Month> {
-    let (input, _) = tag_no_case("may")(input)?;
-    Ok((input, Month::May))
-}
-
-fn june(input: &str) -> IResult<&str, Month> {
-    let (input, _) = alt((tag_no_case("june"), tag_no_case("jun")))(input)?;
-    Ok((input, Month::June))
-}
-
-fn july(input: &str) -> IResult<&str, Month> {
-    let (input, _) = alt((tag_no_case("july"), tag_no_case("jul")))(input)?;
-    Ok((input, Month::July))
+fn month_as_number(locale: &Locale) -> impl Fn(&str) -> PResult<'_, u32> + '_ {
+    move |input| {
+        let (input, month_enum) = month(locale)(input)?;
+        Ok((input, month_enum.number_from_month()))
+    }
 }
 
-fn august(input: &str) -> IResult<&str, Month> {
-    let (input, _) = alt((
-        tag_no_case("august"),
-        tag_no_case("augus"),
-        tag_no_case("augu"),
-        tag_no_case("aug"),
-    ))(input)?;
-    Ok((input, Month::August))
-}
-
-fn september(input: &str) -> IResult<&str, Month> {
-    let (input, _) = alt((
-        tag_no_case("september"),
-        tag_no_case("septembe"),
-        tag_no_case("septemb"),
-        tag_no_case("septem"),
-        tag_no_case("septe"),
-        tag_no_case("sept"),
-        tag_no_case("sep"),
-    ))(input)?;
-    Ok((input, Month::September))
+// The value of `digits`, read at `input`, unless there are too many of them for a u32
+fn digits_value<'a>(
+    input: &'a str,
+    digits: &str,
+) -> std::result::Result<u32, nom::Err<ParseFailure<'a>>> {
+    digits
+        .parse()
+        .map_err(|_| nom::Err::Error(ParseFailure::expecting(input, Expected::SmallerNumber)))
 }
 
-fn november(input: &str) -> IResult<&str, Month> {
-    let (input, _) = alt((
-        tag_no_case("november"),
-        tag_no_case("novembe"),
-        tag_no_case("novemb"),
-        tag_no_case("novem"),
-        tag_no_case("nove"),
-        tag_no_case("nov"),
-    ))(input)?;
-    Ok((input, Month::November))
+pub(crate) fn number(input: &str) -> PResult<'_, u32> {
+    let (rest, digits) = digit1(input)?;
+    Ok((rest, digits_value(input, digits)?))
 }
 
-fn october(input: &str) -> IResult<&str, Month> {
-    let (input, _) = alt((
-        tag_no_case("october"),
-        tag_no_case("octobe"),
-        tag_no_case("octob"),
-        tag_no_case("octo"),
-        tag_no_case("oct"),
-    ))(input)?;
-    Ok((input, Month::October))
+fn time_trailer(input: &str) -> PResult<'_, TimeTrailer> {
+    let (input, _) = space1(input)?;
+    alt((
+        map(time_duration, |(start, end)| {
+            TimeTrailer::RangeRelativeEnd(start, end)
+        }),
+        map(time_range, |(start, end)| {
+            TimeTrailer::RangeAbsoluteEnd(start, end)
+        }),
+        map(parse_time, TimeTrailer::Time),
+    ))(input)
 }
 
-fn december(input: &str) -> IResult<&str, Month> {
-    let (input, _) = alt((
-        tag_no_case("december"),
-        tag_no_case("decembe"),
-        tag_no_case("decemb"),
-        tag_no_case("decem"),
-        tag_no_case("dece"),
-        tag_no_case("dec"),
-    ))(input)?;
-    Ok((input, Month::December))
+fn absolute_with_time(absolute: Absolute, time: AbsoluteTime) -> Absolute {
+    Absolute {
+        hour: time.hour,
+        minute: time.minute,
+        meridiem: time.meridiem,
+        zone: time.zone,
+        ..absolute
+    }
 }
 
-fn month(input: &str) -> IResult<&str, Month> {
-    alt((
-        january, february, march, april, may, june, july, august, september, october, november,
-        december,
-    ))(input)
+fn dated_spec(date: Absolute, trailer: Option<TimeTrailer>) -> DateTimeSpec {
+    match trailer {
+        None => DateTimeSpec::Absolute(date),
+        Some(TimeTrailer::Time(time)) => DateTimeSpec::Absolute(absolute_with_time(date, time)),
+        Some(TimeTrailer::RangeAbsoluteEnd(start, end)) => {
+            DateTimeSpec::DateTimeRangeAbsoluteStartAbsoluteEnd(
+                absolute_with_time(date, start),
+                end,
+            )
+        }
+        Some(TimeTrailer::RangeRelativeEnd(start, end)) => {
+            DateTimeSpec::DateTimeRangeAbsoluteStartRelativeEnd(
+                absolute_with_time(date, start),
+                end,
+            )
+        }
+    }
 }
 
-fn month_as_number(input: &str) -> IResult<&str, u32> {
-    let (input, month_enum) = month(input)?;
-    Ok((input, month_enum.number_from_month()))
+fn now_relative_future(relative: Relative, trailer: Option<TimeTrailer>) -> DateTimeSpec {
+    match trailer {
+        None => DateTimeSpec::NowRelativeFuture(relative),
+        Some(trailer) => DateTimeSpec::NowRelativeFutureWithTime(relative, trailer),
+    }
 }
 
-fn number(input: &str) -> IResult<&str, u32> {
-    let (input, year_str) = digit1(input)?;
-    let year: u32 = year_str.parse().unwrap();
-    Ok((input, year))
+fn now_relative_past(relative: Relative, trailer: Option<TimeTrailer>) -> DateTimeSpec {
+    match trailer {
+        None => DateTimeSpec::NowRelativePast(relative),
+        Some(trailer) => DateTimeSpec::NowRelativePastWithTime(relative, trailer),
+    }
 }
 
-fn parse_dashed_date(input: &str) -> IResult<&str, DateTimeSpec> {
-    let (input, year) = number(input)?;
+fn dashed_date(input: &str) -> PResult<'_, Absolute> {
+    let (input, year) = field(Field::Year, number)(input)?;
     let (input, _) = tag("-")(input)?;
-    let (input, month) = number(input)?;
+    let (input, month) = field(Field::Month, number)(input)?;
     let (input, _) = tag("-")(input)?;
-    let (input, day) = number(input)?;
+    let (input, day) = field(Field::Day, number)(input)?;
     let result = Absolute {
         year: Some(year),
         month: Some(month),
         day: Some(day),
         ..Default::default()
     };
-    Ok((input, DateTimeSpec::Absolute(result)))
+    Ok((input, result))
+}
+
+fn parse_dashed_date(input: &str) -> PResult<'_, DateTimeSpec> {
+    let (input, result) = dashed_date(input)?;
+    let (input, trailer) = opt(time_trailer)(input)?;
+    Ok((input, dated_spec(result, trailer)))
+}
+
+// "3-2-5" read with the abbreviated year last, as the US (month first) or most of Europe (day
+// first) would.  Only offered when the last number could be an abbreviated year and the first
+// couldn't be a full one.
+fn parse_year_last_dashed_date(month_first: bool) -> impl Fn(&str) -> PResult<'_, DateTimeSpec> {
+    move |input| {
+        let (rest, result) = verify(dashed_date, |date| {
+            date.year.is_some_and(|year| year <= 31) && date.day.is_some_and(|day| day < 10)
+        })(input)?;
+        relabel_from(input, |parsed| match (parsed, month_first) {
+            (Field::Year, true) => Field::Month,
+            (Field::Month, true) => Field::Day,
+            (Field::Year, false) => Field::Day,
+            (Field::Day, _) => Field::Year,
+            (other, _) => other,
+        });
+        let (first, second, year) = (result.year, result.month, result.day);
+        let (month, day) = if month_first {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        let (input, trailer) = opt(time_trailer)(rest)?;
+        let result = Absolute {
+            year,
+            month,
+            day,
+            ..result
+        };
+        Ok((input, dated_spec(result, trailer)))
+    }
 }
 
-fn slashed_date_month_day_year(input: &str) -> IResult<&str, Absolute> {
-    let (input, month) = number(input)?;
+fn slashed_date_month_day_year(input: &str) -> PResult<'_, Absolute> {
+    let (input, month) = field(Field::Month, number)(input)?;
     let (input, _) = tag("/")(input)?;
-    let (input, day) = number(input)?;
+    let (input, day) = field(Field::Day, number)(input)?;
     let (input, _) = tag("/")(input)?;
-    let (input, year) = number(input)?;
+    let (input, year) = field(Field::Year, number)(input)?;
     Ok((
         input,
         Absolute {
@@ -297,10 +276,10 @@ fn slashed_date_month_day_year(input: &str) -> IResult<&str, Absolute> {
     ))
 }
 
-fn slashed_date_month_day(input: &str) -> IResult<&str, Absolute> {
-    let (input, month) = number(input)?;
+fn slashed_date_month_day(input: &str) -> PResult<'_, Absolute> {
+    let (input, month) = field(Field::Month, number)(input)?;
     let (input, _) = tag("/")(input)?;
-    let (input, day) = number(input)?;
+    let (input, day) = field(Field::Day, number)(input)?;
     Ok((
         input,
         Absolute {
@@ -311,115 +290,163 @@ fn slashed_date_month_day(input: &str) -> IResult<&str, Absolute> {
     ))
 }
 
-fn parse_slashed_date(input: &str) -> IResult<&str, DateTimeSpec> {
-    let (input, result) = alt((slashed_date_month_day_year, slashed_date_month_day))(input)?;
-    Ok((input, DateTimeSpec::Absolute(result)))
+// "5/2" and "5/2/3" read the European way, day first
+fn parse_day_first_slashed_date(input: &str) -> PResult<'_, DateTimeSpec> {
+    let (rest, result) = alt((slashed_date_month_day_year, slashed_date_month_day))(input)?;
+    relabel_from(input, |parsed| match parsed {
+        Field::Month => Field::Day,
+        Field::Day => Field::Month,
+        other => other,
+    });
+    let (input, trailer) = opt(time_trailer)(rest)?;
+    let result = Absolute {
+        month: result.day,
+        day: result.month,
+        ..result
+    };
+    Ok((input, dated_spec(result, trailer)))
 }
 
-fn iso_week_number(input: &str) -> IResult<&str, u32> {
-    let (input, _) = tag("w")(input)?;
-    let (input, week_str) = digit1(input)?;
-    let week: u32 = week_str.parse().unwrap();
-    Ok((input, week))
+fn parse_slashed_date(input: &str) -> PResult<'_, DateTimeSpec> {
+    let (input, result) = alt((slashed_date_month_day_year, slashed_date_month_day))(input)?;
+    let (input, trailer) = opt(time_trailer)(input)?;
+    Ok((input, dated_spec(result, trailer)))
 }
 
-fn weekday(input: &str) -> IResult<&str, u32> {
-    let (input, output) = alt((
-        monday, tuesday, wednesday, thursday, friday, saturday, sunday,
-    ))(input)?;
-    let numeric_weekday = output.number_from_monday();
-    Ok((input, numeric_weekday))
+fn iso_week_number(input: &str) -> PResult<'_, u32> {
+    let (input, _) = tag("w")(input)?;
+    let (rest, week_str) = digit1(input).map_err(|_: nom::Err<ParseFailure>| {
+        nom::Err::Error(ParseFailure::expecting(input, Expected::WeekNumber))
+    })?;
+    Ok((rest, digits_value(input, week_str)?))
+}
+
+fn weekday_name(locale: &Locale) -> impl Fn(&str) -> PResult<'_, Weekday> + '_ {
+    move |input| match locale.match_weekday(input) {
+        Some((length, weekday)) => Ok((&input[length..], weekday)),
+        None => Err(nom::Err::Error(ParseFailure::expecting(
+            input,
+            Expected::WeekdayName,
+        ))),
+    }
 }
 
-fn numeric_weekday(input: &str) -> IResult<&str, u32> {
-    let (input, weekday_str) = digit1(input)?;
-    let weekday: u32 = weekday_str.parse().unwrap();
-    Ok((input, weekday))
+fn weekday(locale: &Locale) -> impl Fn(&str) -> PResult<'_, u32> + '_ {
+    move |input| {
+        let (input, output) = weekday_name(locale)(input)?;
+        let numeric_weekday = output.number_from_monday();
+        Ok((input, numeric_weekday))
+    }
 }
 
-fn parse_weekday(input: &str) -> IResult<&str, DateTimeSpec> {
-    let (input, wday) = weekday(input)?;
-    let result = Absolute {
-        weekday: Some(wday),
-        ..Default::default()
-    };
-
-    Ok((input, DateTimeSpec::Absolute(result)))
-}
+fn parse_weekday(locale: &Locale) -> impl Fn(&str) -> PResult<'_, DateTimeSpec> + '_ {
+    move |input| {
+        let (input, wday) = field(Field::Weekday, weekday(locale))(input)?;
+        let (input, trailer) = opt(time_trailer)(input)?;
+        let result = Absolute {
+            weekday: Some(wday),
+            ..Default::default()
+        };
 
-fn iso_week_day(input: &str) -> IResult<&str, u32> {
-    alt((weekday, numeric_weekday))(input)
+        Ok((input, dated_spec(result, trailer)))
+    }
 }
 
-fn parse_iso_date(input: &str) -> IResult<&str, DateTimeSpec> {
-    let (input, year) = number(input)?;
-    let (input, _) = alt((tag("-"), space1))(input)?;
-    let (input, week) = iso_week_number(input)?;
-    let (input, _) = alt((tag("-"), space1))(input)?;
-    let (input, weekday) = iso_week_day(input)?;
-    let result = Absolute {
-        year: Some(year),
-        week: Some(week),
-        weekday: Some(weekday),
-        ..Default::default()
-    };
-    Ok((input, DateTimeSpec::Absolute(result)))
+fn iso_week_day(locale: &Locale) -> impl Fn(&str) -> PResult<'_, u32> + '_ {
+    move |input| alt((weekday(locale), number))(input)
 }
 
-fn parse_month_day_year(input: &str) -> IResult<&str, Absolute> {
-    let (input, month) = month_as_number(input)?;
-    let (input, _) = tag(" ")(input)?;
-    let (input, day) = number(input)?;
-    let (input, _) = tag(" ")(input)?;
-    let (input, year) = number(input)?;
-    Ok((
-        input,
-        Absolute {
-            month: Some(month),
-            day: Some(day),
+fn parse_iso_date(locale: &Locale) -> impl Fn(&str) -> PResult<'_, DateTimeSpec> + '_ {
+    move |input| {
+        let (input, year) = field(Field::Year, number)(input)?;
+        let (input, _) = alt((tag("-"), space1))(input)?;
+        let (input, week) = field(Field::Week, iso_week_number)(input)?;
+        let (input, _) = alt((tag("-"), space1))(input)?;
+        let (input, weekday) = field(Field::Weekday, iso_week_day(locale))(input)?;
+        let (input, trailer) = opt(time_trailer)(input)?;
+        let result = Absolute {
             year: Some(year),
+            week: Some(week),
+            weekday: Some(weekday),
             ..Default::default()
-        },
-    ))
+        };
+        Ok((input, dated_spec(result, trailer)))
+    }
 }
 
-fn parse_month_day(input: &str) -> IResult<&str, Absolute> {
-    let (input, month) = month_as_number(input)?;
-    let (input, _) = tag(" ")(input)?;
-    let (input, day) = number(input)?;
-    Ok((
-        input,
-        Absolute {
-            month: Some(month),
-            day: Some(day),
-            ..Default::default()
-        },
-    ))
+// Succeeds if the number just parsed isn't the hour of a time
+fn not_hour(input: &str) -> PResult<'_, ()> {
+    not(alt((tag(":"), recognize(meridiem))))(input)
+}
+
+fn parse_month_day_year(locale: &Locale) -> impl Fn(&str) -> PResult<'_, Absolute> + '_ {
+    move |input| {
+        let (input, month) = field(Field::Month, month_as_number(locale))(input)?;
+        let (input, _) = tag(" ")(input)?;
+        let (input, day) = field(Field::Day, number)(input)?;
+        let (input, _) = tag(" ")(input)?;
+        let (input, year) = field(Field::Year, number)(input)?;
+        // "sep 15 9:30" is a month, a day and a time, not a year followed by garbage
+        let (input, _) = not_hour(input)?;
+        Ok((
+            input,
+            Absolute {
+                month: Some(month),
+                day: Some(day),
+                year: Some(year),
+                ..Default::default()
+            },
+        ))
+    }
 }
 
-fn parse_month_day_optional_year(input: &str) -> IResult<&str, DateTimeSpec> {
-    let (input, result) = alt((parse_month_day_year, parse_month_day))(input)?;
-    Ok((input, DateTimeSpec::Absolute(result)))
+fn parse_month_day(locale: &Locale) -> impl Fn(&str) -> PResult<'_, Absolute> + '_ {
+    move |input| {
+        let (input, month) = field(Field::Month, month_as_number(locale))(input)?;
+        let (input, _) = tag(" ")(input)?;
+        let (input, day) = field(Field::Day, number)(input)?;
+        // "mar 3pm" can only be the Spanish or French Tuesday at 3pm
+        let (input, _) = not_hour(input)?;
+        Ok((
+            input,
+            Absolute {
+                month: Some(month),
+                day: Some(day),
+                ..Default::default()
+            },
+        ))
+    }
+}
+
+fn parse_month_day_optional_year(
+    locale: &Locale,
+) -> impl Fn(&str) -> PResult<'_, DateTimeSpec> + '_ {
+    move |input| {
+        let (input, result) = alt((parse_month_day_year(locale), parse_month_day(locale)))(input)?;
+        let (input, trailer) = opt(time_trailer)(input)?;
+        Ok((input, dated_spec(result, trailer)))
+    }
 }
 
-pub fn hour_minute_meridiem(input: &str) -> IResult<&str, AbsoluteTime> {
-    let (input, hour) = number(input)?;
+pub fn hour_minute_meridiem(input: &str) -> PResult<'_, AbsoluteTime> {
+    let (input, hour) = field(Field::Hour, number)(input)?;
     let (input, _) = tag(":")(input)?;
-    let (input, minute) = number(input)?;
-    let (input, meridiem) = meridiem(input)?;
+    let (input, minute) = field(Field::Minute, number)(input)?;
+    let (input, meridiem) = field(Field::Meridiem, meridiem)(input)?;
     Ok((
         input,
         AbsoluteTime {
             hour: Some(hour),
             minute: Some(minute),
             meridiem: Some(meridiem),
+            zone: None,
         },
     ))
 }
 
-pub fn hour_meridiem(input: &str) -> IResult<&str, AbsoluteTime> {
-    let (input, hour) = number(input)?;
-    let (input, meridiem) = meridiem(input)?;
+pub fn hour_meridiem(input: &str) -> PResult<'_, AbsoluteTime> {
+    let (input, hour) = field(Field::Hour, number)(input)?;
+    let (input, meridiem) = field(Field::Meridiem, meridiem)(input)?;
     Ok((
         input,
         AbsoluteTime {
@@ -430,10 +457,10 @@ pub fn hour_meridiem(input: &str) -> IResult<&str, AbsoluteTime> {
     ))
 }
 
-pub fn hour_minute(input: &str) -> IResult<&str, AbsoluteTime> {
-    let (input, hour) = number(input)?;
+pub fn hour_minute(input: &str) -> PResult<'_, AbsoluteTime> {
+    let (input, hour) = field(Field::Hour, number)(input)?;
     let (input, _) = tag(":")(input)?;
-    let (input, minute) = number(input)?;
+    let (input, minute) = field(Field::Minute, number)(input)?;
     Ok((
         input,
         AbsoluteTime {
@@ -444,28 +471,32 @@ pub fn hour_minute(input: &str) -> IResult<&str, AbsoluteTime> {
     ))
 }
 
-pub fn parse_day_month_hour_minute(input: &str) -> IResult<&str, Absolute> {
-    let (input, day) = number(input)?;
-    let (input, _) = tag(" ")(input)?;
-    let (input, month) = month_as_number(input)?;
-    let (input, _) = tag(" ")(input)?;
-    let (input, hour) = number(input)?;
-    let (input, _) = tag(":")(input)?;
-    let (input, minute) = number(input)?;
-    Ok((
-        input,
-        Absolute {
-            month: Some(month),
-            day: Some(day),
-            hour: Some(hour),
-            minute: Some(minute),
-            ..Default::default()
-        },
-    ))
+pub fn parse_day_month_hour_minute(locale: &Locale) -> impl Fn(&str) -> PResult<'_, Absolute> + '_ {
+    move |input| {
+        let (input, day) = field(Field::Day, number)(input)?;
+        let (input, _) = tag(" ")(input)?;
+        let (input, month) = field(Field::Month, month_as_number(locale))(input)?;
+        let (input, _) = tag(" ")(input)?;
+        let (input, hour) = field(Field::Hour, number)(input)?;
+        let (input, _) = tag(":")(input)?;
+        let (input, minute) = field(Field::Minute, number)(input)?;
+        let (input, zone) = opt(zone)(input)?;
+        Ok((
+            input,
+            Absolute {
+                month: Some(month),
+                day: Some(day),
+                hour: Some(hour),
+                minute: Some(minute),
+                zone,
+                ..Default::default()
+            },
+        ))
+    }
 }
 
-pub fn parse_day_of_month(input: &str) -> IResult<&str, Absolute> {
-    let (input, day) = number(input)?;
+pub fn parse_day_of_month(input: &str) -> PResult<'_, Absolute> {
+    let (input, day) = field(Field::Day, number)(input)?;
     Ok((
         input,
         Absolute {
@@ -475,30 +506,36 @@ pub fn parse_day_of_month(input: &str) -> IResult<&str, Absolute> {
     ))
 }
 
-fn parse_time_as_absolute(input: &str) -> IResult<&str, Absolute> {
+fn parse_time_as_absolute(input: &str) -> PResult<'_, Absolute> {
     let (input, time) = parse_time(input)?;
-    Ok((
-        input,
-        Absolute {
-            hour: time.hour,
-            minute: time.minute,
-            meridiem: time.meridiem,
-            ..Default::default()
-        },
-    ))
+    Ok((input, absolute_with_time(Absolute::default(), time)))
+}
+
+pub fn parse_day_optional_month_optional_hour_minute(
+    locale: &Locale,
+) -> impl Fn(&str) -> PResult<'_, DateTimeSpec> + '_ {
+    move |input| {
+        let (input, result) = alt((
+            parse_day_month_hour_minute(locale),
+            parse_time_as_absolute,
+            parse_day_of_month,
+        ))(input)?;
+        Ok((input, DateTimeSpec::Absolute(result)))
+    }
 }
 
-pub fn parse_day_optional_month_optional_hour_minute(input: &str) -> IResult<&str, DateTimeSpec> {
-    let (input, result) = alt((
-        parse_day_month_hour_minute,
-        parse_time_as_absolute,
-        parse_day_of_month,
-    ))(input)?;
+// "12" as noon rather than the 12th
+fn parse_bare_hour(input: &str) -> PResult<'_, DateTimeSpec> {
+    let (input, hour) = verify(field(Field::Hour, number), |hour| *hour <= 23)(input)?;
+    let result = Absolute {
+        hour: Some(hour),
+        ..Default::default()
+    };
     Ok((input, DateTimeSpec::Absolute(result)))
 }
 
-fn parse_iso_week_number(input: &str) -> IResult<&str, DateTimeSpec> {
-    let (input, week) = iso_week_number(input)?;
+fn parse_iso_week_number(input: &str) -> PResult<'_, DateTimeSpec> {
+    let (input, week) = field(Field::Week, iso_week_number)(input)?;
     let result = Absolute {
         week: Some(week),
         ..Default::default()
@@ -506,7 +543,7 @@ fn parse_iso_week_number(input: &str) -> IResult<&str, DateTimeSpec> {
     Ok((input, DateTimeSpec::Absolute(result)))
 }
 
-fn parse_relative_hours(input: &str) -> IResult<&str, Relative> {
+fn parse_relative_hours(input: &str) -> PResult<'_, Relative> {
     let (input, hours) = number(input)?;
     let (input, _) = tag_no_case("h")(input)?;
     Ok((
@@ -518,7 +555,7 @@ fn parse_relative_hours(input: &str) -> IResult<&str, Relative> {
     ))
 }
 
-fn parse_relative_days(input: &str) -> IResult<&str, Relative> {
+fn parse_relative_days(input: &str) -> PResult<'_, Relative> {
     let (input, days) = number(input)?;
     let (input, _) = tag_no_case("d")(input)?;
     Ok((
@@ -530,7 +567,7 @@ fn parse_relative_days(input: &str) -> IResult<&str, Relative> {
     ))
 }
 
-fn parse_relative_implied_days(input: &str) -> IResult<&str, Relative> {
+fn parse_relative_implied_days(input: &str) -> PResult<'_, Relative> {
     let (input, days) = number(input)?;
     Ok((
         input,
@@ -541,7 +578,7 @@ fn parse_relative_implied_days(input: &str) -> IResult<&str, Relative> {
     ))
 }
 
-fn parse_relative_weeks(input: &str) -> IResult<&str, Relative> {
+fn parse_relative_weeks(input: &str) -> PResult<'_, Relative> {
     let (input, weeks) = number(input)?;
     let (input, _) = tag_no_case("w")(input)?;
     Ok((
@@ -553,7 +590,7 @@ fn parse_relative_weeks(input: &str) -> IResult<&str, Relative> {
     ))
 }
 
-fn parse_relative_months(input: &str) -> IResult<&str, Relative> {
+fn parse_relative_months(input: &str) -> PResult<'_, Relative> {
     let (input, months) = number(input)?;
     let (input, _) = tag_no_case("m")(input)?;
     Ok((
@@ -565,7 +602,7 @@ fn parse_relative_months(input: &str) -> IResult<&str, Relative> {
     ))
 }
 
-fn parse_relative_years(input: &str) -> IResult<&str, Relative> {
+fn parse_relative_years(input: &str) -> PResult<'_, Relative> {
     let (input, years) = number(input)?;
     let (input, _) = tag_no_case("y")(input)?;
     Ok((
@@ -577,77 +614,135 @@ fn parse_relative_years(input: &str) -> IResult<&str, Relative> {
     ))
 }
 
-fn parse_relative_number_weekdays(input: &str) -> IResult<&str, Relative> {
-    let (input, weeks) = number(input)?;
-    let (input, weekdays) = weekday(input)?;
-    Ok((
-        input,
-        Relative {
-            weeks: Some(weeks),
-            weekdays: Some(weekdays),
-            ..Default::default()
-        },
-    ))
+fn parse_relative_number_weekdays(locale: &Locale) -> impl Fn(&str) -> PResult<'_, Relative> + '_ {
+    move |input| {
+        let (input, weeks) = field(Field::Weeks, number)(input)?;
+        let (input, weekdays) = field(Field::Weekdays, weekday(locale))(input)?;
+        Ok((
+            input,
+            Relative {
+                weeks: Some(weeks),
+                weekdays: Some(weekdays),
+                ..Default::default()
+            },
+        ))
+    }
 }
 
-fn parse_relative_implied_one_weekday(input: &str) -> IResult<&str, Relative> {
-    let (input, weekdays) = weekday(input)?;
-    Ok((
-        input,
-        Relative {
-            weekdays: Some(weekdays),
-            ..Default::default()
-        },
-    ))
+fn parse_relative_implied_one_weekday(
+    locale: &Locale,
+) -> impl Fn(&str) -> PResult<'_, Relative> + '_ {
+    move |input| {
+        let (input, weekdays) = field(Field::Weekdays, weekday(locale))(input)?;
+        Ok((
+            input,
+            Relative {
+                weekdays: Some(weekdays),
+                ..Default::default()
+            },
+        ))
+    }
+}
+
+fn parse_relative_weekdays(locale: &Locale) -> impl Fn(&str) -> PResult<'_, Relative> + '_ {
+    move |input| {
+        alt((
+            parse_relative_number_weekdays(locale),
+            parse_relative_implied_one_weekday(locale),
+        ))(input)
+    }
 }
 
-fn parse_relative_weekdays(input: &str) -> IResult<&str, Relative> {
+fn parse_relative_unit(input: &str) -> PResult<'_, Relative> {
     alt((
-        parse_relative_number_weekdays,
-        parse_relative_implied_one_weekday,
+        field(Field::Hours, parse_relative_hours),
+        field(Field::Days, parse_relative_days),
+        field(Field::Weeks, parse_relative_weeks),
+        field(Field::Months, parse_relative_months),
+        field(Field::Years, parse_relative_years),
     ))(input)
 }
 
-fn parse_relative(input: &str) -> IResult<&str, Relative> {
-    let (input, relative) = alt((
-        parse_relative_hours,
-        parse_relative_days,
-        parse_relative_weeks,
-        parse_relative_months,
-        parse_relative_years,
-        parse_relative_weekdays,
-        parse_relative_implied_days,
-    ))(input)?;
+// Combines two partial offsets, or returns None if both set the same unit
+fn merge_relative(left: &Relative, right: &Relative) -> Option<Relative> {
+    fn merge_field(left: Option<u32>, right: Option<u32>) -> Option<Option<u32>> {
+        match (left, right) {
+            (Some(_), Some(_)) => None,
+            _ => Some(left.or(right)),
+        }
+    }
+    Some(Relative {
+        hours: merge_field(left.hours, right.hours)?,
+        days: merge_field(left.days, right.days)?,
+        weeks: merge_field(left.weeks, right.weeks)?,
+        weekdays: merge_field(left.weekdays, right.weekdays)?,
+        months: merge_field(left.months, right.months)?,
+        years: merge_field(left.years, right.years)?,
+    })
+}
+
+// "+1w3d", "-2y6m": one or more units, each appearing at most once
+fn parse_relative_units(input: &str) -> PResult<'_, Relative> {
+    let (mut input, mut relative) = parse_relative_unit(input)?;
+    while let Ok((rest, unit)) = parse_relative_unit(input) {
+        match merge_relative(&relative, &unit) {
+            Some(merged) => relative = merged,
+            None => break,
+        }
+        input = rest;
+    }
     Ok((input, relative))
 }
 
-fn dot_plus_relative(input: &str) -> IResult<&str, DateTimeSpec> {
-    let (input, _) = tag(".")(input)?;
-    let (input, _) = tag("+")(input)?;
-    let (input, relative) = parse_relative(input)?;
-    Ok((input, DateTimeSpec::NowRelativeFuture(relative)))
+fn parse_relative(locale: &Locale) -> impl Fn(&str) -> PResult<'_, Relative> + '_ {
+    move |input| {
+        // Weekdays go first so that "+2mon" isn't read as two months followed by "on"
+        let (input, relative) = alt((
+            parse_relative_weekdays(locale),
+            parse_relative_units,
+            field(Field::Days, parse_relative_implied_days),
+        ))(input)?;
+        Ok((input, relative))
+    }
 }
 
-fn plus_relative(input: &str) -> IResult<&str, DateTimeSpec> {
-    let (input, _) = tag("+")(input)?;
-    let (input, relative) = parse_relative(input)?;
-    Ok((input, DateTimeSpec::NowRelativeFuture(relative)))
+fn dot_plus_relative(locale: &Locale) -> impl Fn(&str) -> PResult<'_, DateTimeSpec> + '_ {
+    move |input| {
+        let (input, _) = tag(".")(input)?;
+        let (input, _) = tag("+")(input)?;
+        let (input, relative) = parse_relative(locale)(input)?;
+        Ok((input, DateTimeSpec::NowRelativeFuture(relative)))
+    }
 }
 
-fn dot_minus_relative(input: &str) -> IResult<&str, DateTimeSpec> {
-    let (input, _) = tag(".")(input)?;
-    let (input, _) = tag("+")(input)?;
-    let (input, relative) = parse_relative(input)?;
-    Ok((input, DateTimeSpec::NowRelativePast(relative)))
+fn plus_relative(locale: &Locale) -> impl Fn(&str) -> PResult<'_, DateTimeSpec> + '_ {
+    move |input| {
+        let (input, _) = tag("+")(input)?;
+        let (input, relative) = parse_relative(locale)(input)?;
+        let (input, trailer) = opt(time_trailer)(input)?;
+        Ok((input, now_relative_future(relative, trailer)))
+    }
 }
 
-fn minus_relative(input: &str) -> IResult<&str, DateTimeSpec> {
-    let (input, _) = tag("-")(input)?;
-    let (input, relative) = parse_relative(input)?;
-    Ok((input, DateTimeSpec::NowRelativePast(relative)))
+fn dot_minus_relative(locale: &Locale) -> impl Fn(&str) -> PResult<'_, DateTimeSpec> + '_ {
+    move |input| {
+        let (input, _) = tag(".")(input)?;
+        let (input, _) = tag("+")(input)?;
+        let (input, relative) = parse_relative(locale)(input)?;
+        Ok((input, DateTimeSpec::NowRelativePast(relative)))
+    }
+}
+
+fn minus_relative(locale: &Locale) -> impl Fn(&str) -> PResult<'_, DateTimeSpec> + '_ {
+    move |input| {
+        let (input, _) = tag("-")(input)?;
+        let (input, relative) = parse_relative(locale)(input)?;
+        let (input, trailer) = opt(time_trailer)(input)?;
+        Ok((input, now_relative_past(relative, trailer)))
+    }
 }
 
-fn dot_relative(input: &str) -> IResult<&str, DateTimeSpec> {
+fn dot_relative(input: &str) -> PResult<'_, DateTimeSpec> {
     let (input, _) = tag(".")(input)?;
     let relative = Relative {
         ..Default::default()
@@ -655,90 +750,377 @@ fn dot_relative(input: &str) -> IResult<&str, DateTimeSpec> {
     Ok((input, DateTimeSpec::NowRelativeFuture(relative)))
 }
 
-fn plus_plus_relative(input: &str) -> IResult<&str, DateTimeSpec> {
-    let (input, _) = tag("++")(input)?;
-    let (input, relative) = parse_relative(input)?;
-    Ok((input, DateTimeSpec::DefaultRelativeFuture(relative)))
+// Like any other date, a keyword may be followed by a time: "tomorrow 3pm", "today noon"
+fn today(input: &str) -> PResult<'_, DateTimeSpec> {
+    let (input, _) = alt((tag_no_case("today"), tag_no_case("now")))(input)?;
+    let (input, trailer) = opt(time_trailer)(input)?;
+    Ok((input, now_relative_future(Relative::default(), trailer)))
+}
+
+fn tomorrow(input: &str) -> PResult<'_, DateTimeSpec> {
+    let (input, _) = field(Field::Days, tag_no_case("tomorrow"))(input)?;
+    let (input, trailer) = opt(time_trailer)(input)?;
+    let relative = Relative {
+        days: Some(1),
+        ..Default::default()
+    };
+    Ok((input, now_relative_future(relative, trailer)))
+}
+
+fn yesterday(input: &str) -> PResult<'_, DateTimeSpec> {
+    let (input, _) = field(Field::Days, tag_no_case("yesterday"))(input)?;
+    let (input, trailer) = opt(time_trailer)(input)?;
+    let relative = Relative {
+        days: Some(1),
+        ..Default::default()
+    };
+    Ok((input, now_relative_past(relative, trailer)))
+}
+
+fn parse_keyword(input: &str) -> PResult<'_, DateTimeSpec> {
+    alt((today, tomorrow, yesterday))(input)
 }
 
-fn minus_minus_relative(input: &str) -> IResult<&str, DateTimeSpec> {
-    let (input, _) = tag("--")(input)?;
-    let (input, relative) = parse_relative(input)?;
-    Ok((input, DateTimeSpec::DefaultRelativePast(relative)))
+fn plus_plus_relative(locale: &Locale) -> impl Fn(&str) -> PResult<'_, DateTimeSpec> + '_ {
+    move |input| {
+        let (input, _) = tag("++")(input)?;
+        let (input, relative) = parse_relative(locale)(input)?;
+        Ok((input, DateTimeSpec::DefaultRelativeFuture(relative)))
+    }
+}
+
+fn minus_minus_relative(locale: &Locale) -> impl Fn(&str) -> PResult<'_, DateTimeSpec> + '_ {
+    move |input| {
+        let (input, _) = tag("--")(input)?;
+        let (input, relative) = parse_relative(locale)(input)?;
+        Ok((input, DateTimeSpec::DefaultRelativePast(relative)))
+    }
 }
 
-fn ante_meridiem(input: &str) -> IResult<&str, Meridiem> {
+fn ante_meridiem(input: &str) -> PResult<'_, Meridiem> {
     let (input, _) = tag_no_case("am")(input)?;
     Ok((input, Meridiem::AM))
 }
 
-fn poste_meridiem(input: &str) -> IResult<&str, Meridiem> {
+fn poste_meridiem(input: &str) -> PResult<'_, Meridiem> {
     let (input, _) = tag_no_case("pm")(input)?;
     Ok((input, Meridiem::PM))
 }
 
-fn meridiem(input: &str) -> IResult<&str, Meridiem> {
+fn meridiem(input: &str) -> PResult<'_, Meridiem> {
     alt((ante_meridiem, poste_meridiem))(input)
 }
 
-fn parse_time(input: &str) -> IResult<&str, AbsoluteTime> {
-    alt((hour_minute_meridiem, hour_meridiem, hour_minute))(input)
+fn noon(input: &str) -> PResult<'_, AbsoluteTime> {
+    let (input, _) = fields(&[Field::Hour, Field::Minute], tag_no_case("noon"))(input)?;
+    Ok((
+        input,
+        AbsoluteTime {
+            hour: Some(12),
+            minute: Some(0),
+            meridiem: None,
+            zone: None,
+        },
+    ))
+}
+
+fn midnight(input: &str) -> PResult<'_, AbsoluteTime> {
+    let (input, _) = fields(&[Field::Hour, Field::Minute], tag_no_case("midnight"))(input)?;
+    Ok((
+        input,
+        AbsoluteTime {
+            hour: Some(0),
+            minute: Some(0),
+            meridiem: None,
+            zone: None,
+        },
+    ))
+}
+
+// A time of day as on a wall clock, without a zone
+pub(crate) fn wall_time(input: &str) -> PResult<'_, AbsoluteTime> {
+    alt((
+        hour_minute_meridiem,
+        hour_meridiem,
+        hour_minute,
+        noon,
+        midnight,
+    ))(input)
+}
+
+// Characters zone names are made of, past the first letter
+fn is_zone_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "_+-/".contains(c)
+}
+
+// "UTC" in any case is the IANA zone of that name, so that it prints the way it was written
+fn utc(input: &str) -> PResult<'_, Zone> {
+    let (input, zone) = alt((
+        map(tag_no_case("utc"), |_| Zone::Named(Tz::UTC)),
+        map(tag_no_case("z"), |_| Zone::Offset(0)),
+    ))(input)?;
+    let (input, _) = not(satisfy(|c| c.is_ascii_alphanumeric() || c == '/'))(input)?;
+    Ok((input, zone))
+}
+
+fn two_digits(input: &str) -> PResult<'_, u32> {
+    let (rest, digits) = take_while_m_n(2, 2, |c: char| c.is_ascii_digit())(input)?;
+    Ok((rest, digits_value(input, digits)?))
+}
+
+// "+02:00", "-0530" or "+02"
+fn utc_offset(input: &str) -> PResult<'_, Zone> {
+    let (input, sign) = alt((map(tag("+"), |_| 1), map(tag("-"), |_| -1)))(input)?;
+    let (input, hours) = verify(two_digits, |hours| *hours < 24)(input)?;
+    let (input, minutes) = opt(preceded(
+        opt(tag(":")),
+        verify(two_digits, |minutes| *minutes < 60),
+    ))(input)?;
+    let (input, _) = not(satisfy(|c| c.is_ascii_alphanumeric()))(input)?;
+    let seconds = hours * 3600 + minutes.unwrap_or(0) * 60;
+    Ok((input, Zone::Offset(sign * seconds as i32)))
+}
+
+fn zone_name(input: &str) -> PResult<'_, Zone> {
+    let failure = || nom::Err::Error(ParseFailure::expecting(input, Expected::Zone));
+    let name: PResult<'_, &str> = recognize(pair(
+        satisfy(|c| c.is_ascii_alphabetic()),
+        take_while(is_zone_name_char),
+    ))(input);
+    let (rest, name) = name.map_err(|_| failure())?;
+    let zone: Tz = name.parse().map_err(|_| failure())?;
+    Ok((rest, Zone::Named(zone)))
 }
 
-fn parse_time_range(input: &str) -> IResult<&str, DateTimeSpec> {
-    let (input, start) = parse_time(input)?;
+fn offset_or_zone_name(input: &str) -> PResult<'_, Zone> {
+    alt((utc_offset, zone_name))(input)
+}
+
+// A zone following a time: "14:00Z", "14:00 UTC", "9am +02:00", "10:00 Europe/Warsaw".  Only
+// "Z" and "UTC" may come right after the time, as "9:00-10:00" and "9:00+1:30" are ranges.
+fn zone(input: &str) -> PResult<'_, Zone> {
+    alt((
+        preceded(space0, field(Field::Zone, utc)),
+        preceded(space1, field(Field::Zone, offset_or_zone_name)),
+    ))(input)
+}
+
+pub(crate) fn parse_time(input: &str) -> PResult<'_, AbsoluteTime> {
+    let (input, time) = wall_time(input)?;
+    let (input, zone) = opt(zone)(input)?;
+    Ok((input, AbsoluteTime { zone, ..time }))
+}
+
+// The zone is written once, after the end
+fn time_range(input: &str) -> PResult<'_, (AbsoluteTime, AbsoluteTime)> {
+    let (input, start) = wall_time(input)?;
     let (input, _) = tag("-")(input)?;
     let (input, _) = opt(tag("-"))(input)?;
-    let (input, end) = parse_time(input)?;
+    let (input, end) = range_end(wall_time)(input)?;
+    let (input, zone) = opt(zone)(input)?;
+    Ok((input, (AbsoluteTime { zone, ..start }, end)))
+}
+
+fn parse_time_range(input: &str) -> PResult<'_, DateTimeSpec> {
+    let (input, (start, end)) = time_range(input)?;
     Ok((
         input,
         DateTimeSpec::TimeRangeAbsoluteStartAbsoluteEnd(start, end),
     ))
 }
 
-fn parse_duration(input: &str) -> IResult<&str, RelativeTime> {
-    let (input, hours) = number(input)?;
+fn parse_duration(input: &str) -> PResult<'_, RelativeTime> {
+    let (input, hours) = field(Field::DurationHours, number)(input)?;
     let (input, _) = tag(":")(input)?;
-    let (input, minutes) = number(input)?;
+    let (input, minutes) = field(Field::DurationMinutes, number)(input)?;
     Ok((
         input,
         RelativeTime {
             hours: Some(hours),
             minutes: Some(minutes),
-            ..Default::default()
         },
     ))
 }
 
-fn parse_time_duration(input: &str) -> IResult<&str, DateTimeSpec> {
-    let (input, start) = parse_time(input)?;
+fn time_duration(input: &str) -> PResult<'_, (AbsoluteTime, RelativeTime)> {
+    let (input, start) = wall_time(input)?;
     let (input, _) = tag("+")(input)?;
     let (input, duration) = parse_duration(input)?;
+    let (input, zone) = opt(zone)(input)?;
+    Ok((input, (AbsoluteTime { zone, ..start }, duration)))
+}
+
+fn parse_time_duration(input: &str) -> PResult<'_, DateTimeSpec> {
+    let (input, (start, duration)) = time_duration(input)?;
     Ok((
         input,
         DateTimeSpec::TimeRangeAbsoluteStartRelativeEnd(start, duration),
     ))
 }
 
+pub(crate) type Production<'a> = (
+    &'static str,
+    Box<dyn Fn(&str) -> PResult<'_, DateTimeSpec> + 'a>,
+);
+
+// The top-level productions, named, in the order parse() tries them
+pub(crate) fn productions(locale: &Locale) -> Vec<Production<'_>> {
+    vec![
+        ("dashed date", Box::new(parse_dashed_date)),
+        ("slashed date", Box::new(parse_slashed_date)),
+        ("ISO week date", Box::new(parse_iso_date(locale))),
+        // Before weekdays, so that the Spanish "mar 15" is March 15 and not Tuesday
+        (
+            "month and day",
+            Box::new(parse_month_day_optional_year(locale)),
+        ),
+        ("weekday", Box::new(parse_weekday(locale))),
+        ("time with duration", Box::new(parse_time_duration)),
+        ("time range", Box::new(parse_time_range)),
+        (
+            "day of month or time",
+            Box::new(parse_day_optional_month_optional_hour_minute(locale)),
+        ),
+        ("ISO week", Box::new(parse_iso_week_number)),
+        (
+            "offset into the future from now",
+            Box::new(dot_plus_relative(locale)),
+        ),
+        (
+            "offset into the past from now",
+            Box::new(dot_minus_relative(locale)),
+        ),
+        ("now", Box::new(dot_relative)),
+        ("offset into the future", Box::new(plus_relative(locale))),
+        ("offset into the past", Box::new(minus_relative(locale))),
+        (
+            "offset into the future from default",
+            Box::new(plus_plus_relative(locale)),
+        ),
+        (
+            "offset into the past from default",
+            Box::new(minus_minus_relative(locale)),
+        ),
+        ("keyword", Box::new(parse_keyword)),
+    ]
+}
+
+// Readings parse() never picks because an earlier production wins, but which
+// are plausible elsewhere
+pub(crate) fn alternative_productions() -> Vec<Production<'static>> {
+    vec![
+        (
+            "day-first slashed date",
+            Box::new(parse_day_first_slashed_date),
+        ),
+        (
+            "month-first dashed date",
+            Box::new(parse_year_last_dashed_date(true)),
+        ),
+        (
+            "day-first dashed date",
+            Box::new(parse_year_last_dashed_date(false)),
+        ),
+        ("hour", Box::new(parse_bare_hour)),
+    ]
+}
+
+// Like alt() over productions(), with each failure labelled by its production
+fn date_time_spec(locale: &Locale) -> impl Fn(&str) -> PResult<'_, DateTimeSpec> + '_ {
+    move |input| {
+        let mut failure = ParseFailure::from_error_kind(input, ErrorKind::Alt);
+        for (name, production) in productions(locale) {
+            match production(input) {
+                Err(nom::Err::Error(err)) => {
+                    failure = failure.or(ParseFailure::add_context(input, name, err))
+                }
+                result => return result,
+            }
+        }
+        Err(nom::Err::Error(failure))
+    }
+}
+
+pub fn parse_with_locale<'a>(input: &'a str, locale: &Locale) -> IResult<&'a str, DateTimeSpec> {
+    date_time_spec(locale)(input).map_err(|err| err.map(Into::into))
+}
+
+// Month and weekday names the word at the start of `rest` may be a misspelling of, closest
+// first, if that's where one was expected
+fn suggestions(locale: &Locale, rest: &str, expected: &[Expected]) -> Vec<String> {
+    let end = rest
+        .find(|c: char| !c.is_alphabetic())
+        .unwrap_or(rest.len());
+    let word = &rest[..end];
+    if word.is_empty() {
+        return Vec::new();
+    }
+    let mut candidates = Vec::new();
+    if expected.contains(&Expected::MonthName) {
+        candidates.extend(locale.suggest_month(word));
+    }
+    if expected.contains(&Expected::WeekdayName) {
+        candidates.extend(locale.suggest_weekday(word));
+    }
+    candidates.sort_by_key(|(distance, _)| *distance);
+    let mut result: Vec<String> = Vec::new();
+    for (_, name) in candidates {
+        if !result.iter().any(|known| known == name) {
+            result.push(name.to_string());
+        }
+    }
+    result
+}
+
+// Like parse_with_locale, but on failure reports where the input stopped
+// matching, which productions got that far, what they expected there and, for
+// misspelled month or weekday names, what the input may have meant
+pub fn parse_str_with_locale<'a>(
+    input: &'a str,
+    locale: &Locale,
+) -> Result<(&'a str, DateTimeSpec)> {
+    date_time_spec(locale)(input).map_err(|err| {
+        into_error(input, err, |rest, expected| {
+            suggestions(locale, rest, expected)
+        })
+    })
+}
+
+pub(crate) fn english() -> &'static Locale {
+    static ENGLISH: OnceLock<Locale> = OnceLock::new();
+    ENGLISH.get_or_init(Locale::english)
+}
+
+// Like parse_str_with_locale, but the spec has to make up the whole input, give
+// or take surrounding whitespace
+pub fn parse_strict_with_locale(input: &str, locale: &Locale) -> Result<DateTimeSpec> {
+    // Offsets in errors are relative to the untrimmed input, since the failure's
+    // remaining input is a suffix of it either way
+    let (rest, spec) = date_time_spec(locale)(input.trim_start()).map_err(|err| {
+        into_error(input, err, |rest, expected| {
+            suggestions(locale, rest, expected)
+        })
+    })?;
+    let leftover = rest.trim_end();
+    if !leftover.is_empty() {
+        return Err(OrgModeDateTimeError::TrailingInput {
+            offset: input.len() - rest.len(),
+            remainder: leftover.to_string(),
+        });
+    }
+    Ok(spec)
+}
+
 pub fn parse(input: &str) -> IResult<&str, DateTimeSpec> {
-    alt((
-        parse_dashed_date,
-        parse_slashed_date,
-        parse_iso_date,
-        parse_weekday,
-        parse_month_day_optional_year,
-        parse_time_duration,
-        parse_time_range,
-        parse_day_optional_month_optional_hour_minute,
-        parse_iso_week_number,
-        dot_plus_relative,
-        dot_minus_relative,
-        dot_relative,
-        plus_relative,
-        minus_relative,
-        plus_plus_relative,
-        minus_minus_relative,
-    ))(input)
+    parse_with_locale(input, english())
+}
+
+pub fn parse_str(input: &str) -> Result<(&str, DateTimeSpec)> {
+    parse_str_with_locale(input, english())
+}
+
+pub fn parse_strict(input: &str) -> Result<DateTimeSpec> {
+    parse_strict_with_locale(input, english())
 }
 
 #[cfg(test)]
@@ -747,77 +1129,101 @@ mod test_parsers_bottom_up {
 
     #[test]
     fn test_weekday_terminals() {
-        assert_eq!(monday("monday").unwrap(), ("", Weekday::Mon));
-        assert_eq!(tuesday("tuesday").unwrap(), ("", Weekday::Tue));
-        assert_eq!(wednesday("wednesday").unwrap(), ("", Weekday::Wed));
-        assert_eq!(thursday("thursday").unwrap(), ("", Weekday::Thu));
-        assert_eq!(friday("friday").unwrap(), ("", Weekday::Fri));
-        assert_eq!(saturday("saturday").unwrap(), ("", Weekday::Sat));
-        assert_eq!(sunday("sunday").unwrap(), ("", Weekday::Sun));
+        assert_eq!(
+            weekday_name(english())("monday").unwrap(),
+            ("", Weekday::Mon)
+        );
+        assert_eq!(
+            weekday_name(english())("tuesday").unwrap(),
+            ("", Weekday::Tue)
+        );
+        assert_eq!(
+            weekday_name(english())("wednesday").unwrap(),
+            ("", Weekday::Wed)
+        );
+        assert_eq!(
+            weekday_name(english())("thursday").unwrap(),
+            ("", Weekday::Thu)
+        );
+        assert_eq!(
+            weekday_name(english())("friday").unwrap(),
+            ("", Weekday::Fri)
+        );
+        assert_eq!(
+            weekday_name(english())("saturday").unwrap(),
+            ("", Weekday::Sat)
+        );
+        assert_eq!(
+            weekday_name(english())("sunday").unwrap(),
+            ("", Weekday::Sun)
+        );
     }
 
     #[test]
     fn test_weekday_nonterminal() {
         assert_eq!(
-            weekday("mon").unwrap(),
+            weekday(english())("mon").unwrap(),
             ("", Weekday::Mon.number_from_monday())
         );
         assert_eq!(
-            weekday("tue").unwrap(),
+            weekday(english())("tue").unwrap(),
             ("", Weekday::Tue.number_from_monday())
         );
         assert_eq!(
-            weekday("wed").unwrap(),
+            weekday(english())("wed").unwrap(),
             ("", Weekday::Wed.number_from_monday())
         );
         assert_eq!(
-            weekday("thu").unwrap(),
+            weekday(english())("thu").unwrap(),
             ("", Weekday::Thu.number_from_monday())
         );
         assert_eq!(
-            weekday("fri").unwrap(),
+            weekday(english())("fri").unwrap(),
             ("", Weekday::Fri.number_from_monday())
         );
         assert_eq!(
-            weekday("sat").unwrap(),
+            weekday(english())("sat").unwrap(),
             ("", Weekday::Sat.number_from_monday())
         );
         assert_eq!(
-            weekday("sun").unwrap(),
+            weekday(english())("sun").unwrap(),
             ("", Weekday::Sun.number_from_monday())
         );
     }
 
     #[test]
     fn test_month_terminals() {
-        assert_eq!(january("january").unwrap(), ("", Month::January));
-        assert_eq!(february("february").unwrap(), ("", Month::February));
-        assert_eq!(march("march").unwrap(), ("", Month::March));
-        assert_eq!(april("april").unwrap(), ("", Month::April));
-        assert_eq!(may("may").unwrap(), ("", Month::May));
-        assert_eq!(june("june").unwrap(), ("", Month::June));
-        assert_eq!(july("july").unwrap(), ("", Month::July));
-        assert_eq!(august("august").unwrap(), ("", Month::August));
-        assert_eq!(september("september").unwrap(), ("", Month::September));
-        assert_eq!(october("october").unwrap(), ("", Month::October));
-        assert_eq!(november("november").unwrap(), ("", Month::November));
-        assert_eq!(december("december").unwrap(), ("", Month::December));
+        assert_eq!(month(english())("january").unwrap(), ("", Month::January));
+        assert_eq!(month(english())("february").unwrap(), ("", Month::February));
+        assert_eq!(month(english())("march").unwrap(), ("", Month::March));
+        assert_eq!(month(english())("april").unwrap(), ("", Month::April));
+        assert_eq!(month(english())("may").unwrap(), ("", Month::May));
+        assert_eq!(month(english())("june").unwrap(), ("", Month::June));
+        assert_eq!(month(english())("july").unwrap(), ("", Month::July));
+        assert_eq!(month(english())("august").unwrap(), ("", Month::August));
+        assert_eq!(
+            month(english())("september").unwrap(),
+            ("", Month::September)
+        );
+        assert_eq!(month(english())("october").unwrap(), ("", Month::October));
+        assert_eq!(month(english())("november").unwrap(), ("", Month::November));
+        assert_eq!(month(english())("december").unwrap(), ("", Month::December));
     }
 
     #[test]
     fn test_month_nonterminal() {
-        assert_eq!(month("jan").unwrap(), ("", Month::January));
-        assert_eq!(month("feb").unwrap(), ("", Month::February));
-        assert_eq!(month("mar").unwrap(), ("", Month::March));
-        assert_eq!(month("apr").unwrap(), ("", Month::April));
-        assert_eq!(month("may").unwrap(), ("", Month::May));
-        assert_eq!(month("jun").unwrap(), ("", Month::June));
-        assert_eq!(month("jul").unwrap(), ("", Month::July));
-        assert_eq!(month("aug").unwrap(), ("", Month::August));
-        assert_eq!(month("sep").unwrap(), ("", Month::September));
-        assert_eq!(month("oct").unwrap(), ("", Month::October));
-        assert_eq!(month("nov").unwrap(), ("", Month::November));
-        assert_eq!(month("dec").unwrap(), ("", Month::December));
+        assert_eq!(month(english())("jan").unwrap(), ("", Month::January));
+        assert_eq!(month(english())("feb").unwrap(), ("", Month::February));
+        assert_eq!(month(english())("mar").unwrap(), ("", Month::March));
+        assert_eq!(month(english())("apr").unwrap(), ("", Month::April));
+        assert_eq!(month(english())("may").unwrap(), ("", Month::May));
+        assert_eq!(month(english())("jun").unwrap(), ("", Month::June));
+        assert_eq!(month(english())("jul").unwrap(), ("", Month::July));
+        assert_eq!(month(english())("aug").unwrap(), ("", Month::August));
+        assert_eq!(month(english())("sep").unwrap(), ("", Month::September));
+        assert_eq!(month(english())("oct").unwrap(), ("", Month::October));
+        assert_eq!(month(english())("nov").unwrap(), ("", Month::November));
+        assert_eq!(month(english())("dec").unwrap(), ("", Month::December));
     }
 
     #[test]
@@ -855,7 +1261,7 @@ mod test_parsers_bottom_up {
     #[test]
     fn test_iso_date() {
         assert_eq!(
-            parse_iso_date("2012 w4 fri").unwrap(),
+            parse_iso_date(english())("2012 w4 fri").unwrap(),
             (
                 "",
                 DateTimeSpec::Absolute(Absolute {
@@ -868,7 +1274,7 @@ mod test_parsers_bottom_up {
         );
 
         assert_eq!(
-            parse_iso_date("2012-w04-5").unwrap(),
+            parse_iso_date(english())("2012-w04-5").unwrap(),
             (
                 "",
                 DateTimeSpec::Absolute(Absolute {
@@ -1036,6 +1442,209 @@ mod test_parse {
         );
     }
 
+    #[test]
+    fn test_zone() {
+        let zoned = |input, zone| {
+            let (rest, spec) = parse(input).unwrap();
+            assert_eq!(rest, "", "{:?} should be read completely", input);
+            match spec {
+                DateTimeSpec::Absolute(absolute) => assert_eq!(absolute.zone, Some(zone)),
+                other => panic!("expected {:?} to be absolute, got {:?}", input, other),
+            }
+        };
+        zoned("14:00Z", Zone::Offset(0));
+        zoned("14:00 UTC", Zone::Named(Tz::UTC));
+        zoned("9am +02:00", Zone::Offset(7200));
+        zoned("9am -0530", Zone::Offset(-19800));
+        zoned("22 sept 0:34 +01", Zone::Offset(3600));
+        zoned("2006-06-13 14:00 utc", Zone::Named(Tz::UTC));
+        zoned(
+            "fri 10:00 Europe/Warsaw",
+            Zone::Named(chrono_tz::Europe::Warsaw),
+        );
+        assert_eq!(
+            parse("10:00 Mars/Olympus").unwrap(),
+            (
+                " Mars/Olympus",
+                DateTimeSpec::Absolute(Absolute {
+                    hour: Some(10),
+                    minute: Some(0),
+                    ..Default::default()
+                }),
+            )
+        );
+    }
+
+    #[test]
+    fn test_zoned_range() {
+        assert_eq!(
+            parse("9:00-10:00 UTC").unwrap(),
+            (
+                "",
+                DateTimeSpec::TimeRangeAbsoluteStartAbsoluteEnd(
+                    AbsoluteTime {
+                        hour: Some(9),
+                        minute: Some(0),
+                        meridiem: None,
+                        zone: Some(Zone::Named(Tz::UTC))
+                    },
+                    AbsoluteTime {
+                        hour: Some(10),
+                        minute: Some(0),
+                        meridiem: None,
+                        zone: None
+                    }
+                ),
+            )
+        );
+        assert_eq!(
+            parse("fri 9:00+1:30 Z").unwrap(),
+            (
+                "",
+                DateTimeSpec::DateTimeRangeAbsoluteStartRelativeEnd(
+                    Absolute {
+                        weekday: Some(5),
+                        hour: Some(9),
+                        minute: Some(0),
+                        zone: Some(Zone::Offset(0)),
+                        ..Default::default()
+                    },
+                    RelativeTime {
+                        hours: Some(1),
+                        minutes: Some(30),
+                    }
+                ),
+            )
+        );
+    }
+
+    #[test]
+    fn test_date_time() {
+        assert_eq!(
+            parse("2006-06-13 14:00").unwrap(),
+            (
+                "",
+                DateTimeSpec::Absolute(Absolute {
+                    year: Some(2006),
+                    month: Some(6),
+                    day: Some(13),
+                    hour: Some(14),
+                    minute: Some(0),
+                    ..Default::default()
+                }),
+            )
+        );
+        assert_eq!(
+            parse("fri 3pm").unwrap(),
+            (
+                "",
+                DateTimeSpec::Absolute(Absolute {
+                    weekday: Some(5),
+                    hour: Some(3),
+                    meridiem: Some(Meridiem::PM),
+                    ..Default::default()
+                }),
+            )
+        );
+        assert_eq!(
+            parse("sep 15 9:30").unwrap(),
+            (
+                "",
+                DateTimeSpec::Absolute(Absolute {
+                    month: Some(9),
+                    day: Some(15),
+                    hour: Some(9),
+                    minute: Some(30),
+                    ..Default::default()
+                }),
+            )
+        );
+        assert_eq!(
+            parse("sep 15 9am").unwrap(),
+            (
+                "",
+                DateTimeSpec::Absolute(Absolute {
+                    month: Some(9),
+                    day: Some(15),
+                    hour: Some(9),
+                    meridiem: Some(Meridiem::AM),
+                    ..Default::default()
+                }),
+            )
+        );
+        assert_eq!(
+            parse("2/5/3 10:15").unwrap(),
+            (
+                "",
+                DateTimeSpec::Absolute(Absolute {
+                    year: Some(3),
+                    month: Some(2),
+                    day: Some(5),
+                    hour: Some(10),
+                    minute: Some(15),
+                    ..Default::default()
+                }),
+            )
+        );
+        assert_eq!(
+            parse("2012-w04-5 8am").unwrap(),
+            (
+                "",
+                DateTimeSpec::Absolute(Absolute {
+                    year: Some(2012),
+                    week: Some(4),
+                    weekday: Some(5),
+                    hour: Some(8),
+                    meridiem: Some(Meridiem::AM),
+                    ..Default::default()
+                }),
+            )
+        );
+    }
+
+    #[test]
+    fn test_date_time_range() {
+        assert_eq!(
+            parse("2006-06-13 11am-1pm").unwrap(),
+            (
+                "",
+                DateTimeSpec::DateTimeRangeAbsoluteStartAbsoluteEnd(
+                    Absolute {
+                        year: Some(2006),
+                        month: Some(6),
+                        day: Some(13),
+                        hour: Some(11),
+                        meridiem: Some(Meridiem::AM),
+                        ..Default::default()
+                    },
+                    AbsoluteTime {
+                        hour: Some(1),
+                        meridiem: Some(Meridiem::PM),
+                        ..Default::default()
+                    }
+                ),
+            )
+        );
+        assert_eq!(
+            parse("tue 9:00+1:30").unwrap(),
+            (
+                "",
+                DateTimeSpec::DateTimeRangeAbsoluteStartRelativeEnd(
+                    Absolute {
+                        weekday: Some(2),
+                        hour: Some(9),
+                        minute: Some(0),
+                        ..Default::default()
+                    },
+                    RelativeTime {
+                        hours: Some(1),
+                        minutes: Some(30),
+                    }
+                ),
+            )
+        );
+    }
+
     #[test]
     fn test_week_number() {
         assert_eq!(
@@ -1185,6 +1794,64 @@ mod test_parse {
         );
     }
 
+    #[test]
+    fn test_compound_relative() {
+        assert_eq!(
+            parse("+1w3d").unwrap(),
+            (
+                "",
+                DateTimeSpec::NowRelativeFuture(Relative {
+                    weeks: Some(1),
+                    days: Some(3),
+                    ..Default::default()
+                }),
+            )
+        );
+        assert_eq!(
+            parse("-2y6m").unwrap(),
+            (
+                "",
+                DateTimeSpec::NowRelativePast(Relative {
+                    years: Some(2),
+                    months: Some(6),
+                    ..Default::default()
+                }),
+            )
+        );
+        assert_eq!(
+            parse("++1m2d").unwrap(),
+            (
+                "",
+                DateTimeSpec::DefaultRelativeFuture(Relative {
+                    months: Some(1),
+                    days: Some(2),
+                    ..Default::default()
+                }),
+            )
+        );
+        assert_eq!(
+            parse("+1d2d").unwrap(),
+            (
+                "2d",
+                DateTimeSpec::NowRelativeFuture(Relative {
+                    days: Some(1),
+                    ..Default::default()
+                }),
+            )
+        );
+        assert_eq!(
+            parse("+2mon").unwrap(),
+            (
+                "",
+                DateTimeSpec::NowRelativeFuture(Relative {
+                    weeks: Some(2),
+                    weekdays: Some(1),
+                    ..Default::default()
+                }),
+            )
+        );
+    }
+
     #[test]
     fn test_default_plus_days() {
         assert_eq!(
@@ -1235,6 +1902,121 @@ mod test_parse {
         );
     }
 
+    #[test]
+    fn test_keywords() {
+        assert_eq!(
+            parse("today").unwrap(),
+            ("", DateTimeSpec::NowRelativeFuture(Relative::default()))
+        );
+        assert_eq!(parse("NOW").unwrap(), parse(".").unwrap());
+        assert_eq!(
+            parse("Tomorrow").unwrap(),
+            (
+                "",
+                DateTimeSpec::NowRelativeFuture(Relative {
+                    days: Some(1),
+                    ..Default::default()
+                }),
+            )
+        );
+        assert_eq!(
+            parse("yesterday").unwrap(),
+            (
+                "",
+                DateTimeSpec::NowRelativePast(Relative {
+                    days: Some(1),
+                    ..Default::default()
+                }),
+            )
+        );
+        assert_eq!(
+            parse("noon").unwrap(),
+            (
+                "",
+                DateTimeSpec::Absolute(Absolute {
+                    hour: Some(12),
+                    minute: Some(0),
+                    ..Default::default()
+                }),
+            )
+        );
+        assert_eq!(
+            parse("fri Midnight").unwrap(),
+            (
+                "",
+                DateTimeSpec::Absolute(Absolute {
+                    weekday: Some(5),
+                    hour: Some(0),
+                    minute: Some(0),
+                    ..Default::default()
+                }),
+            )
+        );
+    }
+
+    #[test]
+    fn test_keyword_with_time() {
+        assert_eq!(
+            parse("tomorrow noon").unwrap(),
+            (
+                "",
+                DateTimeSpec::NowRelativeFutureWithTime(
+                    Relative {
+                        days: Some(1),
+                        ..Default::default()
+                    },
+                    TimeTrailer::Time(AbsoluteTime {
+                        hour: Some(12),
+                        minute: Some(0),
+                        meridiem: None,
+                        zone: None
+                    })
+                ),
+            )
+        );
+        assert_eq!(
+            parse("today 3pm").unwrap(),
+            (
+                "",
+                DateTimeSpec::NowRelativeFutureWithTime(
+                    Relative::default(),
+                    TimeTrailer::Time(AbsoluteTime {
+                        hour: Some(3),
+                        minute: None,
+                        meridiem: Some(Meridiem::PM),
+                        zone: None
+                    })
+                ),
+            )
+        );
+        assert_eq!(
+            parse("yesterday 9:00+1:30").unwrap(),
+            (
+                "",
+                DateTimeSpec::NowRelativePastWithTime(
+                    Relative {
+                        days: Some(1),
+                        ..Default::default()
+                    },
+                    TimeTrailer::RangeRelativeEnd(
+                        AbsoluteTime {
+                            hour: Some(9),
+                            minute: Some(0),
+                            meridiem: None,
+                            zone: None
+                        },
+                        RelativeTime {
+                            hours: Some(1),
+                            minutes: Some(30),
+                        }
+                    )
+                ),
+            )
+        );
+        assert!(parse_strict("tomorrow midnight").is_ok());
+        assert!(parse_strict("+2d 10:00").is_ok());
+    }
+
     #[test]
     fn test_time_range() {
         assert_eq!(
@@ -1245,12 +2027,14 @@ mod test_parse {
                     AbsoluteTime {
                         meridiem: Some(Meridiem::AM),
                         hour: Some(11),
-                        minute: None
+                        minute: None,
+                        zone: None
                     },
                     AbsoluteTime {
                         meridiem: Some(Meridiem::PM),
                         minute: Some(15),
-                        hour: Some(1)
+                        hour: Some(1),
+                        zone: None
                     }
                 ),
             )
@@ -1263,12 +2047,14 @@ mod test_parse {
                     AbsoluteTime {
                         meridiem: Some(Meridiem::AM),
                         hour: Some(11),
-                        minute: None
+                        minute: None,
+                        zone: None
                     },
                     AbsoluteTime {
                         meridiem: Some(Meridiem::PM),
                         minute: Some(15),
-                        hour: Some(1)
+                        hour: Some(1),
+                        zone: None
                     }
                 ),
             )
@@ -1285,7 +2071,8 @@ mod test_parse {
                     AbsoluteTime {
                         meridiem: Some(Meridiem::AM),
                         hour: Some(11),
-                        minute: None
+                        minute: None,
+                        zone: None
                     },
                     RelativeTime {
                         hours: Some(2),
@@ -1296,3 +2083,242 @@ mod test_parse {
         );
     }
 }
+
+#[cfg(test)]
+mod test_parse_with_locale {
+    use super::*;
+
+    #[test]
+    fn test_german() {
+        let locale = Locale::german();
+        assert_eq!(
+            parse_with_locale("15 März 10:00", &locale).unwrap(),
+            (
+                "",
+                DateTimeSpec::Absolute(Absolute {
+                    month: Some(3),
+                    day: Some(15),
+                    hour: Some(10),
+                    minute: Some(0),
+                    ..Default::default()
+                }),
+            )
+        );
+        assert_eq!(
+            parse_with_locale("Di", &locale).unwrap(),
+            (
+                "",
+                DateTimeSpec::Absolute(Absolute {
+                    weekday: Some(2),
+                    ..Default::default()
+                }),
+            )
+        );
+    }
+
+    #[test]
+    fn test_spanish_month_or_weekday() {
+        let locale = Locale::spanish();
+        assert_eq!(
+            parse_with_locale("mar 15", &locale).unwrap(),
+            (
+                "",
+                DateTimeSpec::Absolute(Absolute {
+                    month: Some(3),
+                    day: Some(15),
+                    ..Default::default()
+                }),
+            )
+        );
+        assert_eq!(
+            parse_with_locale("mar 3pm", &locale).unwrap(),
+            (
+                "",
+                DateTimeSpec::Absolute(Absolute {
+                    weekday: Some(2),
+                    hour: Some(3),
+                    meridiem: Some(Meridiem::PM),
+                    ..Default::default()
+                }),
+            )
+        );
+    }
+
+    #[test]
+    fn test_polish_relative_weekday() {
+        assert_eq!(
+            parse_with_locale("+2piątek", &Locale::polish()).unwrap(),
+            (
+                "",
+                DateTimeSpec::NowRelativeFuture(Relative {
+                    weeks: Some(2),
+                    weekdays: Some(5),
+                    ..Default::default()
+                }),
+            )
+        );
+    }
+
+    #[test]
+    fn test_english_names_rejected() {
+        assert!(parse_with_locale("friday", &Locale::french()).is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_parse_str {
+    use super::*;
+    use crate::error::OrgModeDateTimeError;
+
+    fn unexpected(input: &str) -> (usize, Vec<Expected>, Vec<&'static str>) {
+        match parse_str(input) {
+            Err(OrgModeDateTimeError::UnexpectedInput {
+                offset,
+                expected,
+                productions,
+                ..
+            }) => (offset, expected, productions),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_success() {
+        assert_eq!(
+            parse_str("2006-06-13").unwrap(),
+            (
+                "",
+                DateTimeSpec::Absolute(Absolute {
+                    year: Some(2006),
+                    month: Some(6),
+                    day: Some(13),
+                    ..Default::default()
+                })
+            )
+        );
+    }
+
+    #[test]
+    fn test_iso_week_missing_number() {
+        let (offset, expected, productions) = unexpected("w");
+        assert_eq!(offset, 1);
+        assert_eq!(expected, vec![Expected::WeekNumber]);
+        assert_eq!(productions, vec!["ISO week"]);
+    }
+
+    #[test]
+    fn test_number_too_large() {
+        let (offset, expected, _) = unexpected("12345678901234567890");
+        assert_eq!(offset, 0);
+        assert!(expected.contains(&Expected::SmallerNumber));
+        let (offset, expected, _) = unexpected("+99999999999d");
+        assert_eq!(offset, 1);
+        assert!(expected.contains(&Expected::SmallerNumber));
+        assert!(parse_strict("2006-06-13 9:12345678901234567890").is_err());
+    }
+
+    #[test]
+    fn test_month_without_day() {
+        let (offset, expected, productions) = unexpected("sep x");
+        assert_eq!(offset, 4);
+        assert_eq!(expected, vec![Expected::Digit]);
+        assert_eq!(productions, vec!["month and day"]);
+    }
+
+    #[test]
+    fn test_bare_sign() {
+        let (offset, expected, _) = unexpected("+");
+        assert_eq!(offset, 1);
+        assert!(expected.contains(&Expected::Digit));
+        assert!(expected.contains(&Expected::WeekdayName));
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let (offset, expected, productions) = unexpected("");
+        assert_eq!(offset, 0);
+        assert!(expected.contains(&Expected::Digit));
+        assert!(expected.contains(&Expected::MonthName));
+        assert!(expected.contains(&Expected::Token("+")));
+        assert!(productions.contains(&"keyword"));
+    }
+
+    #[test]
+    fn test_double_sign_without_offset() {
+        let (offset, expected, productions) = unexpected("++");
+        assert_eq!(offset, 2);
+        assert!(expected.contains(&Expected::Digit));
+        assert!(expected.contains(&Expected::WeekdayName));
+        assert_eq!(productions, vec!["offset into the future from default"]);
+    }
+
+    #[test]
+    fn test_message() {
+        assert_eq!(
+            parse_str("sep x").unwrap_err().to_string(),
+            "Unexpected input at offset 4, expected digit"
+        );
+    }
+
+    fn suggestions(input: &str) -> Vec<String> {
+        match parse_str(input) {
+            Err(OrgModeDateTimeError::UnexpectedInput { suggestions, .. }) => suggestions,
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_suggestions() {
+        assert_eq!(suggestions("setp 15"), vec!["sep"]);
+        assert_eq!(suggestions("thrusday"), vec!["thursday"]);
+        assert_eq!(suggestions("xyzzy"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_suggestions_message() {
+        let message = parse_str("thrusday").unwrap_err().to_string();
+        assert!(message.starts_with("Unexpected input at offset 0, expected digit, month name"));
+        assert!(message.ends_with("; did you mean `thursday`?"));
+    }
+
+    #[test]
+    fn test_suggestions_with_locale() {
+        match parse_str_with_locale("jamuar 5", &Locale::german()) {
+            Err(OrgModeDateTimeError::UnexpectedInput { suggestions, .. }) => {
+                assert_eq!(suggestions, vec!["januar"])
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_strict_surrounding_whitespace() {
+        assert_eq!(
+            parse_strict("  12:45 \t").unwrap(),
+            DateTimeSpec::Absolute(Absolute {
+                hour: Some(12),
+                minute: Some(45),
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_strict_trailing_garbage() {
+        match parse_strict(" 12:45xyz ") {
+            Err(OrgModeDateTimeError::TrailingInput { offset, remainder }) => {
+                assert_eq!(offset, 6);
+                assert_eq!(remainder, "xyz");
+            }
+            other => panic!("expected trailing input, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_strict_offset_includes_leading_whitespace() {
+        match parse_strict("  sep x") {
+            Err(OrgModeDateTimeError::UnexpectedInput { offset, .. }) => assert_eq!(offset, 6),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,469 @@
+use std::fmt;
+
+use crate::parser::{
+    Absolute, AbsoluteTime, DateTimeSpec, Meridiem, Relative, RelativeTime, TimeTrailer, Zone,
+};
+
+// Canonical forms, which parse() reads back as an equal value.  Combinations of fields parse()
+// never produces (a year on its own, say) print on a best-effort basis and needn't read back.
+
+const WEEKDAY_NAMES: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+fn write_weekday(f: &mut fmt::Formatter, weekday: u32) -> fmt::Result {
+    match weekday
+        .checked_sub(1)
+        .and_then(|index| WEEKDAY_NAMES.get(index as usize))
+    {
+        Some(name) => write!(f, "{}", name),
+        None => write!(f, "{}", weekday),
+    }
+}
+
+fn write_time(
+    f: &mut fmt::Formatter,
+    hour: Option<u32>,
+    minute: Option<u32>,
+    meridiem: Option<Meridiem>,
+) -> fmt::Result {
+    if let Some(hour) = hour {
+        write!(f, "{}", hour)?;
+    }
+    if let Some(minute) = minute {
+        write!(f, ":{:02}", minute)?;
+    }
+    if let Some(meridiem) = meridiem {
+        write!(f, "{}", meridiem)?;
+    }
+    Ok(())
+}
+
+fn write_zone(f: &mut fmt::Formatter, zone: Option<Zone>) -> fmt::Result {
+    match zone {
+        Some(zone) => write!(f, " {}", zone),
+        None => Ok(()),
+    }
+}
+
+impl fmt::Display for Zone {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Zone::Offset(0) => write!(f, "Z"),
+            Zone::Offset(seconds) => {
+                let sign = if *seconds < 0 { '-' } else { '+' };
+                let minutes = seconds.unsigned_abs() / 60;
+                write!(f, "{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+            }
+            Zone::Named(zone) => write!(f, "{}", zone.name()),
+        }
+    }
+}
+
+impl fmt::Display for Meridiem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Meridiem::AM => write!(f, "am"),
+            Meridiem::PM => write!(f, "pm"),
+        }
+    }
+}
+
+impl fmt::Display for AbsoluteTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_time(f, self.hour, self.minute, self.meridiem)?;
+        write_zone(f, self.zone)
+    }
+}
+
+impl fmt::Display for RelativeTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}:{:02}",
+            self.hours.unwrap_or(0),
+            self.minutes.unwrap_or(0)
+        )
+    }
+}
+
+impl fmt::Display for Absolute {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let has_date = match (self.year, self.month, self.day, self.week, self.weekday) {
+            (None, None, None, None, None) => false,
+            (Some(year), Some(month), Some(day), None, None) => {
+                write!(f, "{}-{}-{}", year, month, day)?;
+                true
+            }
+            (None, Some(month), Some(day), None, None) => {
+                write!(f, "{}/{}", month, day)?;
+                true
+            }
+            (None, None, Some(day), None, None) => {
+                write!(f, "{}", day)?;
+                true
+            }
+            (None, None, None, None, Some(weekday)) => {
+                write_weekday(f, weekday)?;
+                true
+            }
+            (Some(year), None, None, Some(week), Some(weekday)) => {
+                write!(f, "{}-w{}-{}", year, week, weekday)?;
+                true
+            }
+            (None, None, None, Some(week), None) => {
+                write!(f, "w{}", week)?;
+                true
+            }
+            (year, month, day, week, weekday) => {
+                let fields: Vec<String> = [year, month, day, week, weekday]
+                    .iter()
+                    .flatten()
+                    .map(u32::to_string)
+                    .collect();
+                write!(f, "{}", fields.join("-"))?;
+                true
+            }
+        };
+        if has_date && self.hour.is_some() {
+            write!(f, " ")?;
+        }
+        write_time(f, self.hour, self.minute, self.meridiem)?;
+        write_zone(f, self.zone)
+    }
+}
+
+impl fmt::Display for Relative {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(weekdays) = self.weekdays {
+            if let Some(weeks) = self.weeks {
+                write!(f, "{}", weeks)?;
+            }
+            return write_weekday(f, weekdays);
+        }
+        let units = [
+            (self.years, "y"),
+            (self.months, "m"),
+            (self.weeks, "w"),
+            (self.days, "d"),
+            (self.hours, "h"),
+        ];
+        for (count, unit) in units {
+            if let Some(count) = count {
+                write!(f, "{}{}", count, unit)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Printed the way the time ranges are
+impl fmt::Display for TimeTrailer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TimeTrailer::Time(time) => write!(f, "{}", time),
+            TimeTrailer::RangeAbsoluteEnd(start, end) => write!(
+                f,
+                "{}",
+                DateTimeSpec::TimeRangeAbsoluteStartAbsoluteEnd(start.clone(), end.clone())
+            ),
+            TimeTrailer::RangeRelativeEnd(start, duration) => write!(
+                f,
+                "{}",
+                DateTimeSpec::TimeRangeAbsoluteStartRelativeEnd(start.clone(), duration.clone())
+            ),
+        }
+    }
+}
+
+impl fmt::Display for DateTimeSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DateTimeSpec::Absolute(absolute) => write!(f, "{}", absolute),
+            DateTimeSpec::NowRelativeFuture(relative) if *relative == Relative::default() => {
+                write!(f, ".")
+            }
+            DateTimeSpec::NowRelativeFuture(relative) => write!(f, "+{}", relative),
+            DateTimeSpec::NowRelativePast(relative) => write!(f, "-{}", relative),
+            DateTimeSpec::NowRelativeFutureWithTime(relative, trailer)
+                if *relative == Relative::default() =>
+            {
+                write!(f, "today {}", trailer)
+            }
+            DateTimeSpec::NowRelativeFutureWithTime(relative, trailer) => {
+                write!(f, "+{} {}", relative, trailer)
+            }
+            DateTimeSpec::NowRelativePastWithTime(relative, trailer) => {
+                write!(f, "-{} {}", relative, trailer)
+            }
+            DateTimeSpec::DefaultRelativeFuture(relative) => write!(f, "++{}", relative),
+            DateTimeSpec::DefaultRelativePast(relative) => write!(f, "--{}", relative),
+            // A range's zone is written once, at the end
+            DateTimeSpec::TimeRangeAbsoluteStartAbsoluteEnd(start, end) => {
+                let zone = start.zone;
+                let start = AbsoluteTime {
+                    zone: None,
+                    ..start.clone()
+                };
+                write!(f, "{}-{}", start, end)?;
+                write_zone(f, zone)
+            }
+            DateTimeSpec::TimeRangeAbsoluteStartRelativeEnd(start, duration) => {
+                let zone = start.zone;
+                let start = AbsoluteTime {
+                    zone: None,
+                    ..start.clone()
+                };
+                write!(f, "{}+{}", start, duration)?;
+                write_zone(f, zone)
+            }
+            DateTimeSpec::DateTimeRangeAbsoluteStartAbsoluteEnd(start, end) => {
+                let zone = start.zone;
+                let start = Absolute {
+                    zone: None,
+                    ..start.clone()
+                };
+                write!(f, "{}-{}", start, end)?;
+                write_zone(f, zone)
+            }
+            DateTimeSpec::DateTimeRangeAbsoluteStartRelativeEnd(start, duration) => {
+                let zone = start.zone;
+                let start = Absolute {
+                    zone: None,
+                    ..start.clone()
+                };
+                write!(f, "{}+{}", start, duration)?;
+                write_zone(f, zone)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_printer {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::parser::parse_strict;
+
+    fn printed(input: &str) -> String {
+        parse_strict(input).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_canonical_forms() {
+        assert_eq!(printed("2006-06-13 14:00"), "2006-6-13 14:00");
+        assert_eq!(printed("sep 15 2006 9:30"), "2006-9-15 9:30");
+        assert_eq!(printed("sep 15"), "9/15");
+        assert_eq!(printed("22 sept 0:34"), "9/22 0:34");
+        assert_eq!(printed("Friday 3PM"), "fri 3pm");
+        assert_eq!(printed("2012 w4 fri"), "2012-w4-5");
+        assert_eq!(printed("w04"), "w4");
+        assert_eq!(printed("noon"), "12:00");
+        assert_eq!(printed("+3"), "+3d");
+        assert_eq!(printed("tomorrow"), "+1d");
+        assert_eq!(printed("today"), ".");
+        assert_eq!(printed("tomorrow noon"), "+1d 12:00");
+        assert_eq!(printed("today 3pm"), "today 3pm");
+        assert_eq!(printed("yesterday 9:00-10:00 UTC"), "-1d 9:00-10:00 UTC");
+        assert_eq!(printed("--2d1w"), "--1w2d");
+        assert_eq!(printed("++2fri"), "++2fri");
+        assert_eq!(printed("tue 9:00+1:30"), "tue 9:00+1:30");
+        assert_eq!(printed("11am--1pm"), "11am-1pm");
+        assert_eq!(printed("14:00Z"), "14:00 Z");
+        assert_eq!(printed("14:00 utc"), "14:00 UTC");
+        assert_eq!(printed("14:00 Etc/UTC"), "14:00 Etc/UTC");
+        assert_eq!(printed("9am +0200"), "9am +02:00");
+        assert_eq!(printed("fri 9:00-10:00 -05:30"), "fri 9:00-10:00 -05:30");
+        assert_eq!(
+            printed("sep 15 10:00 Europe/Warsaw"),
+            "9/15 10:00 Europe/Warsaw"
+        );
+    }
+
+    fn number() -> impl Strategy<Value = u32> {
+        0..3000u32
+    }
+
+    fn meridiem() -> impl Strategy<Value = Meridiem> {
+        prop_oneof![Just(Meridiem::AM), Just(Meridiem::PM)]
+    }
+
+    fn zone() -> impl Strategy<Value = Option<Zone>> {
+        let last_minute = 23 * 60 + 59;
+        proptest::option::of(prop_oneof![
+            (-last_minute..=last_minute).prop_map(|minutes| Zone::Offset(minutes * 60)),
+            Just(Zone::Named(chrono_tz::Europe::Warsaw)),
+            Just(Zone::Named(chrono_tz::America::PortauPrince)),
+            Just(Zone::Named(chrono_tz::Etc::GMTPlus5)),
+            Just(Zone::Named(chrono_tz::Zulu)),
+            Just(Zone::Named(chrono_tz::UTC)),
+            Just(Zone::Named(chrono_tz::Etc::UTC)),
+            Just(Zone::Named(chrono_tz::GMT)),
+        ])
+    }
+
+    fn wall_time() -> impl Strategy<Value = AbsoluteTime> {
+        prop_oneof![
+            (number(), number()).prop_map(|(hour, minute)| AbsoluteTime {
+                hour: Some(hour),
+                minute: Some(minute),
+                ..Default::default()
+            }),
+            (number(), meridiem()).prop_map(|(hour, meridiem)| AbsoluteTime {
+                hour: Some(hour),
+                meridiem: Some(meridiem),
+                ..Default::default()
+            }),
+            (number(), number(), meridiem()).prop_map(|(hour, minute, meridiem)| AbsoluteTime {
+                hour: Some(hour),
+                minute: Some(minute),
+                meridiem: Some(meridiem),
+                ..Default::default()
+            }),
+        ]
+    }
+
+    fn absolute_time() -> impl Strategy<Value = AbsoluteTime> {
+        (wall_time(), zone()).prop_map(|(time, zone)| AbsoluteTime { zone, ..time })
+    }
+
+    fn relative_time() -> impl Strategy<Value = RelativeTime> {
+        (number(), number()).prop_map(|(hours, minutes)| RelativeTime {
+            hours: Some(hours),
+            minutes: Some(minutes),
+        })
+    }
+
+    // Dates that may be followed by a time
+    fn date() -> impl Strategy<Value = Absolute> {
+        prop_oneof![
+            (number(), number(), number()).prop_map(|(year, month, day)| Absolute {
+                year: Some(year),
+                month: Some(month),
+                day: Some(day),
+                ..Default::default()
+            }),
+            (number(), number()).prop_map(|(month, day)| Absolute {
+                month: Some(month),
+                day: Some(day),
+                ..Default::default()
+            }),
+            (1..=7u32).prop_map(|weekday| Absolute {
+                weekday: Some(weekday),
+                ..Default::default()
+            }),
+            (number(), number(), number()).prop_map(|(year, week, weekday)| Absolute {
+                year: Some(year),
+                week: Some(week),
+                weekday: Some(weekday),
+                ..Default::default()
+            }),
+        ]
+    }
+
+    fn with_time(date: Absolute, time: AbsoluteTime) -> Absolute {
+        Absolute {
+            hour: time.hour,
+            minute: time.minute,
+            meridiem: time.meridiem,
+            zone: time.zone,
+            ..date
+        }
+    }
+
+    fn absolute() -> impl Strategy<Value = Absolute> {
+        prop_oneof![
+            date(),
+            (date(), absolute_time()).prop_map(|(date, time)| with_time(date, time)),
+            absolute_time().prop_map(|time| with_time(Absolute::default(), time)),
+            number().prop_map(|day| Absolute {
+                day: Some(day),
+                ..Default::default()
+            }),
+            number().prop_map(|week| Absolute {
+                week: Some(week),
+                ..Default::default()
+            }),
+        ]
+    }
+
+    fn relative() -> impl Strategy<Value = Relative> {
+        let units = (
+            proptest::option::of(number()),
+            proptest::option::of(number()),
+            proptest::option::of(number()),
+            proptest::option::of(number()),
+            proptest::option::of(number()),
+        )
+            .prop_filter("at least one unit", |units| {
+                units != &(None, None, None, None, None)
+            })
+            .prop_map(|(hours, days, weeks, months, years)| Relative {
+                hours,
+                days,
+                weeks,
+                months,
+                years,
+                weekdays: None,
+            });
+        let weekdays =
+            (proptest::option::of(number()), 1..=7u32).prop_map(|(weeks, weekdays)| Relative {
+                weeks,
+                weekdays: Some(weekdays),
+                ..Default::default()
+            });
+        prop_oneof![units, weekdays]
+    }
+
+    fn time_trailer() -> impl Strategy<Value = TimeTrailer> {
+        prop_oneof![
+            absolute_time().prop_map(TimeTrailer::Time),
+            (absolute_time(), wall_time())
+                .prop_map(|(start, end)| TimeTrailer::RangeAbsoluteEnd(start, end)),
+            (absolute_time(), relative_time())
+                .prop_map(|(start, duration)| TimeTrailer::RangeRelativeEnd(start, duration)),
+        ]
+    }
+
+    fn date_time_spec() -> impl Strategy<Value = DateTimeSpec> {
+        prop_oneof![
+            absolute().prop_map(DateTimeSpec::Absolute),
+            Just(DateTimeSpec::NowRelativeFuture(Relative::default())),
+            relative().prop_map(DateTimeSpec::NowRelativeFuture),
+            relative().prop_map(DateTimeSpec::NowRelativePast),
+            time_trailer().prop_map(|trailer| {
+                DateTimeSpec::NowRelativeFutureWithTime(Relative::default(), trailer)
+            }),
+            (relative(), time_trailer()).prop_map(|(relative, trailer)| {
+                DateTimeSpec::NowRelativeFutureWithTime(relative, trailer)
+            }),
+            (relative(), time_trailer()).prop_map(|(relative, trailer)| {
+                DateTimeSpec::NowRelativePastWithTime(relative, trailer)
+            }),
+            relative().prop_map(DateTimeSpec::DefaultRelativeFuture),
+            relative().prop_map(DateTimeSpec::DefaultRelativePast),
+            (absolute_time(), wall_time()).prop_map(|(start, end)| {
+                DateTimeSpec::TimeRangeAbsoluteStartAbsoluteEnd(start, end)
+            }),
+            (absolute_time(), relative_time()).prop_map(|(start, duration)| {
+                DateTimeSpec::TimeRangeAbsoluteStartRelativeEnd(start, duration)
+            }),
+            (date(), absolute_time(), wall_time()).prop_map(|(date, start, end)| {
+                DateTimeSpec::DateTimeRangeAbsoluteStartAbsoluteEnd(with_time(date, start), end)
+            }),
+            (date(), absolute_time(), relative_time()).prop_map(|(date, start, duration)| {
+                DateTimeSpec::DateTimeRangeAbsoluteStartRelativeEnd(
+                    with_time(date, start),
+                    duration,
+                )
+            }),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_roundtrip(spec in date_time_spec()) {
+            let printed = spec.to_string();
+            prop_assert_eq!(parse_strict(&printed).ok(), Some(spec), "printed as {:?}", printed);
+        }
+    }
+}
@@ -1,14 +1,25 @@
-use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Weekday};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::error::{OrgModeDateTimeError, Result};
-use crate::parser::{Absolute, AbsoluteTime, DateTimeSpec, Meridiem, Relative, RelativeTime};
+use crate::parser::{
+    Absolute, AbsoluteTime, DateTimeSpec, Meridiem, Relative, RelativeTime, TimeTrailer,
+};
+use crate::span::Field;
 
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(tag = "kind", content = "value", rename_all = "snake_case")
+)]
 pub enum RenderedSpec {
     Date(NaiveDate),
     DateTime(NaiveDateTime),
     Week(u32),
     TimeRange(NaiveTime, NaiveTime),
+    DateTimeRange(NaiveDateTime, NaiveDateTime),
 }
 
 fn guess_abbreviated_year(now: NaiveDateTime, year: u32) -> Result<u32> {
@@ -20,61 +31,192 @@ fn guess_abbreviated_year(now: NaiveDateTime, year: u32) -> Result<u32> {
     Ok(result)
 }
 
-fn weekday_from_u32(weekday: u32) -> Weekday {
+fn weekday_from_u32(field: Field, weekday: u32) -> Result<Weekday> {
     match weekday {
-        1 => Weekday::Mon,
-        2 => Weekday::Tue,
-        3 => Weekday::Wed,
-        4 => Weekday::Thu,
-        5 => Weekday::Fri,
-        6 => Weekday::Sat,
-        7 => Weekday::Sun,
-        _ => panic!("weekday out of range: {}", weekday),
+        1 => Ok(Weekday::Mon),
+        2 => Ok(Weekday::Tue),
+        3 => Ok(Weekday::Wed),
+        4 => Ok(Weekday::Thu),
+        5 => Ok(Weekday::Fri),
+        6 => Ok(Weekday::Sat),
+        7 => Ok(Weekday::Sun),
+        _ => Err(OrgModeDateTimeError::FieldOutOfRange {
+            field,
+            value: weekday,
+        }),
     }
 }
 
-fn apply_meridiem(hour: u32, meridiem: Option<Meridiem>) -> u32 {
+// 12am is midnight and 12pm noon
+pub(crate) fn apply_meridiem(hour: u32, meridiem: Option<Meridiem>) -> u32 {
     match meridiem {
-        None | Some(Meridiem::AM) => hour,
-        Some(Meridiem::PM) => hour + 12,
+        None => hour,
+        Some(Meridiem::AM) => hour % 12,
+        Some(Meridiem::PM) => hour % 12 + 12,
     }
 }
 
-fn render_absolute(absolute: Absolute, baseline: NaiveDateTime) -> Result<RenderedSpec> {
-    match absolute {
+// `fields` name the hour and minute in errors
+fn absolute_time(
+    hour: Option<u32>,
+    minute: Option<u32>,
+    meridiem: Option<Meridiem>,
+    fields: [Field; 2],
+) -> Result<Option<NaiveTime>> {
+    let [hour_field, minute_field] = fields;
+    let hour = match hour {
+        Some(hour) => hour,
+        None => return Ok(None),
+    };
+    let minute = minute.unwrap_or(0);
+    if minute >= 60 {
+        return Err(OrgModeDateTimeError::FieldOutOfRange {
+            field: minute_field,
+            value: minute,
+        });
+    }
+    NaiveTime::from_hms_opt(apply_meridiem(hour, meridiem), minute, 0)
+        .map(Some)
+        .ok_or(OrgModeDateTimeError::FieldOutOfRange {
+            field: hour_field,
+            value: hour,
+        })
+}
+
+fn start_time(time: &AbsoluteTime) -> Result<Option<NaiveTime>> {
+    absolute_time(
+        time.hour,
+        time.minute,
+        time.meridiem,
+        [Field::Hour, Field::Minute],
+    )
+}
+
+fn end_time(time: &AbsoluteTime) -> Result<Option<NaiveTime>> {
+    absolute_time(
+        time.hour,
+        time.minute,
+        time.meridiem,
+        [Field::EndHour, Field::EndMinute],
+    )
+}
+
+fn unsupported_range(start: Vec<Field>, end: Vec<Field>) -> OrgModeDateTimeError {
+    let end = end.into_iter().map(Field::as_range_end);
+    OrgModeDateTimeError::UnsupportedFields(start.into_iter().chain(end).collect())
+}
+
+// Which occurrence an incomplete date or time ("sep 15", "12", "fri", "12:45") stands for,
+// like org-read-date-prefer-future
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum DatePreference {
+    // The first one not before the baseline, so "fri" on a Saturday is six days ahead
+    #[default]
+    Future,
+    // The last one not after the baseline, so "fri" on a Saturday is yesterday
+    Past,
+    // Whichever is closer to the baseline, the future one on a tie
+    Nearest,
+}
+
+// What ZonedRenderer does with a local time skipped when clocks go forward, like 2:30 on a day
+// they jump from 2:00 to 3:00
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum NonexistentTime {
+    // Move it forward by the length of the gap, so 2:30 becomes 3:30
+    ShiftForward,
+    #[default]
+    Error,
+}
+
+// What ZonedRenderer does with a local time repeated when clocks go back, like 2:30 on a day
+// they return from 3:00 to 2:00
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum AmbiguousTime {
+    // The first occurrence, still on the earlier offset
+    Earliest,
+    // The second occurrence, on the later offset
+    Latest,
+    #[default]
+    Error,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct RenderOptions {
+    pub prefer: DatePreference,
+    pub nonexistent: NonexistentTime,
+    pub ambiguous: AmbiguousTime,
+}
+
+// Picks one of `candidates`, which should surround the baseline, by `prefer`.  Without a time
+// a candidate on the baseline's date counts as neither past nor future.
+fn resolve(
+    candidates: impl Iterator<Item = Option<NaiveDate>>,
+    time: Option<NaiveTime>,
+    baseline: NaiveDateTime,
+    prefer: DatePreference,
+) -> Result<NaiveDate> {
+    let moment = |date: &NaiveDate| date.and_time(time.unwrap_or(baseline.time()));
+    let candidates = candidates.flatten();
+    let date = match prefer {
+        DatePreference::Future => candidates.filter(|date| moment(date) >= baseline).min(),
+        DatePreference::Past => candidates.filter(|date| moment(date) <= baseline).max(),
+        DatePreference::Nearest => candidates.min_by_key(|date| {
+            let distance = moment(date) - baseline;
+            (distance.abs(), distance < Duration::zero())
+        }),
+    };
+    date.ok_or(OrgModeDateTimeError::DateOutOfRange)
+}
+
+fn render_absolute(
+    absolute: Absolute,
+    baseline: NaiveDateTime,
+    options: &RenderOptions,
+) -> Result<RenderedSpec> {
+    let prefer = options.prefer;
+    let unsupported = || OrgModeDateTimeError::UnsupportedFields(absolute.populated_fields());
+    if absolute.hour.is_none() && (absolute.minute.is_some() || absolute.meridiem.is_some()) {
+        return Err(unsupported());
+    }
+    let time = absolute_time(
+        absolute.hour,
+        absolute.minute,
+        absolute.meridiem,
+        [Field::Hour, Field::Minute],
+    )?;
+    let date = match absolute {
         Absolute {
             year: Some(year),
             month: Some(month),
             day: Some(day),
             week: None,
             weekday: None,
-            hour: None,
-            minute: None,
-            meridiem: None,
             ..
-        } => {
-            let date = NaiveDate::from_ymd(
-                guess_abbreviated_year(baseline, year)?.try_into()?,
-                month,
-                day,
-            );
-            Ok(RenderedSpec::Date(date))
-        }
+        } => NaiveDate::from_ymd_opt(
+            guess_abbreviated_year(baseline, year)?.try_into()?,
+            month,
+            day,
+        )
+        .ok_or(OrgModeDateTimeError::FieldOutOfRange {
+            field: Field::Day,
+            value: day,
+        })?,
         Absolute {
             year: None,
             month: None,
             day: Some(day),
             week: None,
             weekday: None,
-            hour: None,
-            minute: None,
-            meridiem: None,
+            ..
         } => {
-            let mut date = NaiveDate::from_ymd(baseline.year(), baseline.month(), day);
-            if date.and_hms(baseline.hour(), baseline.minute(), baseline.second()) < baseline {
-                date = NaiveDate::from_ymd(baseline.year(), baseline.month() + 1, day);
-            }
-            Ok(RenderedSpec::Date(date))
+            // Months around this one, some of which may be too short
+            let first_of_month = baseline.date().with_day(1);
+            let months = (-2..=2).map(|months| {
+                add_months(first_of_month?, months, MonthEnd::Error)
+                    .and_then(|date| date.with_day(day))
+            });
+            resolve(months, time, baseline, prefer)?
         }
         Absolute {
             year: None,
@@ -82,15 +224,14 @@ fn render_absolute(absolute: Absolute, baseline: NaiveDateTime) -> Result<Render
             day: Some(day),
             week: None,
             weekday: None,
-            hour: None,
-            minute: None,
-            meridiem: None,
+            ..
         } => {
-            let mut date = NaiveDate::from_ymd(baseline.year(), month, day);
-            if date.and_hms(baseline.hour(), baseline.minute(), baseline.second()) < baseline {
-                date = NaiveDate::from_ymd(baseline.year() + 1, month, day);
-            }
-            Ok(RenderedSpec::Date(date))
+            // Feb 29 may be up to eight years away
+            let years = (-8..=8).map(|years| {
+                let year = baseline.year().checked_add(years)?;
+                NaiveDate::from_ymd_opt(year, month, day)
+            });
+            resolve(years, time, baseline, prefer)?
         }
         Absolute {
             year: None,
@@ -98,19 +239,12 @@ fn render_absolute(absolute: Absolute, baseline: NaiveDateTime) -> Result<Render
             day: None,
             week: None,
             weekday: None,
-            hour: Some(hour),
-            minute: Some(minute),
-            meridiem: None,
+            hour: Some(_),
+            ..
         } => {
-            let mut datetime =
-                NaiveDate::from_ymd(baseline.year(), baseline.month(), baseline.day())
-                    .and_hms(hour, minute, 0);
-            if datetime < baseline {
-                datetime =
-                    NaiveDate::from_ymd(datetime.year(), datetime.month(), datetime.day() + 1)
-                        .and_hms(datetime.hour(), datetime.minute(), datetime.second());
-            }
-            Ok(RenderedSpec::DateTime(datetime))
+            let days =
+                (-1..=1).map(|days| baseline.date().checked_add_signed(Duration::days(days)));
+            resolve(days, time, baseline, prefer)?
         }
         Absolute {
             year: None,
@@ -118,34 +252,16 @@ fn render_absolute(absolute: Absolute, baseline: NaiveDateTime) -> Result<Render
             day: None,
             week: None,
             weekday: Some(weekday),
-            hour: None,
-            minute: None,
-            meridiem: None,
-        } => {
-            let mut date = baseline.date();
-            while date.weekday().number_from_monday() != weekday {
-                date = date.succ();
-            }
-            Ok(RenderedSpec::Date(date))
-        }
-        Absolute {
-            year: None,
-            month: Some(month),
-            day: Some(day),
-            week: None,
-            weekday: None,
-            hour: Some(hour),
-            minute: Some(minute),
-            meridiem: None,
+            ..
         } => {
-            let mut datetime =
-                NaiveDate::from_ymd(baseline.year(), month, day).and_hms(hour, minute, 0);
-            if datetime < baseline {
-                datetime =
-                    NaiveDate::from_ymd(datetime.year() + 1, datetime.month(), datetime.day())
-                        .and_hms(datetime.hour(), datetime.minute(), datetime.second());
-            }
-            Ok(RenderedSpec::DateTime(datetime))
+            let weekday = weekday_from_u32(Field::Weekday, weekday)?;
+            let days = (-7..=7).map(|days| {
+                baseline
+                    .date()
+                    .checked_add_signed(Duration::days(days))
+                    .filter(|date| date.weekday() == weekday)
+            });
+            resolve(days, time, baseline, prefer)?
         }
         Absolute {
             year: None,
@@ -156,71 +272,92 @@ fn render_absolute(absolute: Absolute, baseline: NaiveDateTime) -> Result<Render
             hour: None,
             minute: None,
             meridiem: None,
-        } => Ok(RenderedSpec::Week(week)),
+            zone: None,
+        } => return Ok(RenderedSpec::Week(week)),
         Absolute {
             year: Some(year),
             month: None,
             day: None,
             week: Some(week),
             weekday: Some(weekday),
-            hour: None,
-            minute: None,
-            meridiem: None,
-        } => {
-            let date = NaiveDate::from_isoywd(year.try_into()?, week, weekday_from_u32(weekday));
-            Ok(RenderedSpec::Date(date))
-        }
-        _ => unreachable!(),
+            ..
+        } => NaiveDate::from_isoywd_opt(
+            year.try_into()?,
+            week,
+            weekday_from_u32(Field::Weekday, weekday)?,
+        )
+        .ok_or(OrgModeDateTimeError::FieldOutOfRange {
+            field: Field::Week,
+            value: week,
+        })?,
+        _ => return Err(unsupported()),
+    };
+    match time {
+        Some(time) => Ok(RenderedSpec::DateTime(date.and_time(time))),
+        None => Ok(RenderedSpec::Date(date)),
+    }
+}
+
+// What shifting by months or years does when it lands on a day the month doesn't have, like
+// Jan 31 + 1m
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum MonthEnd {
+    // No such date, as the date prompt has it
+    Error,
+    // The last day of the month instead, as Org repeats "<2006-01-31 Tue +1m>"
+    Clamp,
+}
+
+fn add_months(date: NaiveDate, months: i64, month_end: MonthEnd) -> Option<NaiveDate> {
+    let month0 = i64::from(date.year()) * 12 + i64::from(date.month0()) + months;
+    let year = i32::try_from(month0.div_euclid(12)).ok()?;
+    let month = u32::try_from(month0.rem_euclid(12)).ok()? + 1;
+    let days = match month_end {
+        MonthEnd::Error => date.day()..=date.day(),
+        MonthEnd::Clamp => date.day().min(28)..=date.day(),
+    };
+    days.rev()
+        .find_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+}
+
+// Shifts `baseline` by every unit set in `relative`, in the order: years, months, weeks, days,
+// hours.  `sign` is 1 for future offsets and -1 for past ones.  Returns None if the result is
+// out of range, or with MonthEnd::Error if an intermediate date doesn't exist.
+pub(crate) fn shift_by_relative(
+    baseline: NaiveDateTime,
+    relative: &Relative,
+    sign: i64,
+    month_end: MonthEnd,
+) -> Option<NaiveDateTime> {
+    let mut datetime = baseline;
+    if let Some(years) = relative.years {
+        datetime = add_months(datetime.date(), sign * 12 * i64::from(years), month_end)?
+            .and_time(datetime.time());
+    }
+    if let Some(months) = relative.months {
+        datetime = add_months(datetime.date(), sign * i64::from(months), month_end)?
+            .and_time(datetime.time());
+    }
+    if let Some(weeks) = relative.weeks {
+        datetime = datetime.checked_add_signed(Duration::weeks(sign * i64::from(weeks)))?;
+    }
+    if let Some(days) = relative.days {
+        datetime = datetime.checked_add_signed(Duration::days(sign * i64::from(days)))?;
     }
+    if let Some(hours) = relative.hours {
+        datetime = datetime.checked_add_signed(Duration::hours(sign * i64::from(hours)))?;
+    }
+    Some(datetime)
 }
 
-fn render_relative_future(relative: Relative, baseline: NaiveDateTime) -> Result<RenderedSpec> {
+pub(crate) fn render_relative_future(
+    relative: Relative,
+    baseline: NaiveDateTime,
+    month_end: MonthEnd,
+) -> Result<RenderedSpec> {
+    let unrepresentable =
+        || OrgModeDateTimeError::UnrepresentableFutureRelativeDate(relative.clone());
     match relative {
-        // today
-        Relative {
-            hours: None,
-            days: None,
-            weeks: None,
-            weekdays: None,
-            months: None,
-            years: None,
-        } => Ok(RenderedSpec::Date(baseline.date())),
-        // hours
-        Relative {
-            hours: Some(hours),
-            days: None,
-            weeks: None,
-            weekdays: None,
-            months: None,
-            years: None,
-        } => {
-            let date = baseline + Duration::hours(hours.into());
-            Ok(RenderedSpec::DateTime(date))
-        }
-        // days
-        Relative {
-            hours: None,
-            days: Some(days),
-            weeks: None,
-            weekdays: None,
-            months: None,
-            years: None,
-        } => {
-            let date = baseline.date() + Duration::days(days.into());
-            Ok(RenderedSpec::Date(date))
-        }
-        // weeks
-        Relative {
-            hours: None,
-            days: None,
-            weeks: Some(weeks),
-            weekdays: None,
-            months: None,
-            years: None,
-        } => {
-            let date = baseline.date() + Duration::weeks(weeks.into());
-            Ok(RenderedSpec::Date(date))
-        }
         // weekdays
         Relative {
             days: None,
@@ -230,102 +367,42 @@ fn render_relative_future(relative: Relative, baseline: NaiveDateTime) -> Result
             years: None,
             weekdays: Some(weekdays),
         } => {
+            let weekday = weekday_from_u32(Field::Weekdays, weekdays)?;
             let mut date = baseline.date();
-            while date.weekday().number_from_monday() != weekdays {
-                date = date.succ();
+            while date.weekday() != weekday {
+                date = date.succ_opt().ok_or_else(unrepresentable)?;
             }
             let nweeks = weeks.unwrap_or(1);
-            date += Duration::weeks(nweeks.into());
+            date = date
+                .checked_add_signed(Duration::weeks(nweeks.into()))
+                .ok_or_else(unrepresentable)?;
             Ok(RenderedSpec::Date(date))
         }
-        // months
-        Relative {
-            hours: None,
-            days: None,
-            weeks: None,
-            weekdays: None,
-            months: Some(mut months),
-            years: None,
-        } => {
-            months %= 12;
-            let calculated_month0 = baseline.month0() + months;
-            let (year, month) = if calculated_month0 > 11 {
-                (baseline.year() + 1, calculated_month0 % 12 + 1)
+        // today, or any combination of years, months, weeks, days and hours
+        Relative { weekdays: None, .. } => {
+            let with_time = relative.hours.is_some();
+            let datetime =
+                shift_by_relative(baseline, &relative, 1, month_end).ok_or_else(unrepresentable)?;
+            if with_time {
+                Ok(RenderedSpec::DateTime(datetime))
             } else {
-                (baseline.year(), calculated_month0 + 1)
-            };
-            let date = NaiveDate::from_ymd_opt(year, month, baseline.day()).ok_or(
-                OrgModeDateTimeError::UnrepresentableFutureRelativeDate(relative),
-            )?;
-            Ok(RenderedSpec::Date(date))
-        }
-        // years
-        Relative {
-            hours: None,
-            days: None,
-            weeks: None,
-            weekdays: None,
-            months: None,
-            years: Some(years),
-        } => {
-            let date = NaiveDate::from_ymd(
-                baseline.year() + i32::try_from(years)?,
-                baseline.month(),
-                baseline.day(),
-            );
-            Ok(RenderedSpec::Date(date))
+                Ok(RenderedSpec::Date(datetime.date()))
+            }
         }
-        _ => unreachable!(),
+        _ => Err(OrgModeDateTimeError::UnsupportedFields(
+            relative.populated_fields(),
+        )),
     }
 }
 
-fn render_relative_past(relative: Relative, baseline: NaiveDateTime) -> Result<RenderedSpec> {
+pub(crate) fn render_relative_past(
+    relative: Relative,
+    baseline: NaiveDateTime,
+    month_end: MonthEnd,
+) -> Result<RenderedSpec> {
+    let unrepresentable =
+        || OrgModeDateTimeError::UnrepresentablePastRelativeDate(relative.clone());
     match relative {
-        // today
-        Relative {
-            hours: None,
-            days: None,
-            weeks: None,
-            weekdays: None,
-            months: None,
-            years: None,
-        } => Ok(RenderedSpec::Date(baseline.date())),
-        // hours
-        Relative {
-            hours: Some(hours),
-            days: None,
-            weeks: None,
-            weekdays: None,
-            months: None,
-            years: None,
-        } => {
-            let date = baseline - Duration::hours(hours.into());
-            Ok(RenderedSpec::DateTime(date))
-        }
-        // days
-        Relative {
-            hours: None,
-            days: Some(days),
-            weeks: None,
-            weekdays: None,
-            months: None,
-            years: None,
-        } => {
-            let date = baseline.date() - Duration::days(days.into());
-            Ok(RenderedSpec::Date(date))
-        }
-        // weeks
-        Relative {
-            hours: None,
-            days: None,
-            weeks: Some(weeks),
-            weekdays: None,
-            months: None,
-            years: None,
-        } => {
-            let date = baseline.date() - Duration::weeks(weeks.into());
-            Ok(RenderedSpec::Date(date))
-        }
         // weekdays
         Relative {
             days: None,
@@ -335,53 +412,31 @@ fn render_relative_past(relative: Relative, baseline: NaiveDateTime) -> Result<R
             months: None,
             years: None,
         } => {
-            let weekday: Weekday = weekday_from_u32(weekdays);
+            let weekday = weekday_from_u32(Field::Weekdays, weekdays)?;
             let mut date = baseline.date();
             while date.weekday() != weekday {
-                date = date.pred();
+                date = date.pred_opt().ok_or_else(unrepresentable)?;
             }
-            let nweeks = weeks.unwrap_or(1) - 1;
-            date -= Duration::weeks(nweeks.into());
+            let nweeks = weeks.unwrap_or(1).saturating_sub(1);
+            date = date
+                .checked_sub_signed(Duration::weeks(nweeks.into()))
+                .ok_or_else(unrepresentable)?;
             Ok(RenderedSpec::Date(date))
         }
-        // months
-        Relative {
-            hours: None,
-            days: None,
-            weeks: None,
-            weekdays: None,
-            months: Some(mut months),
-            years: None,
-        } => {
-            months %= 12;
-            let calculated_month0 = i32::try_from(baseline.month0())? - i32::try_from(months)?;
-            let (year, month) = if calculated_month0 < 0 {
-                (baseline.year() - 1, -calculated_month0 % 12)
+        // today, or any combination of years, months, weeks, days and hours
+        Relative { weekdays: None, .. } => {
+            let with_time = relative.hours.is_some();
+            let datetime = shift_by_relative(baseline, &relative, -1, month_end)
+                .ok_or_else(unrepresentable)?;
+            if with_time {
+                Ok(RenderedSpec::DateTime(datetime))
             } else {
-                (baseline.year(), calculated_month0 + 1)
-            };
-            let date = NaiveDate::from_ymd_opt(year, u32::try_from(month)?, baseline.day()).ok_or(
-                OrgModeDateTimeError::UnrepresentablePastRelativeDate(relative),
-            )?;
-            Ok(RenderedSpec::Date(date))
-        }
-        // years
-        Relative {
-            hours: None,
-            days: None,
-            weeks: None,
-            weekdays: None,
-            months: None,
-            years: Some(years),
-        } => {
-            let date = NaiveDate::from_ymd(
-                baseline.year() - i32::try_from(years)?,
-                baseline.month(),
-                baseline.day(),
-            );
-            Ok(RenderedSpec::Date(date))
+                Ok(RenderedSpec::Date(datetime.date()))
+            }
         }
-        _ => unreachable!(),
+        _ => Err(OrgModeDateTimeError::UnsupportedFields(
+            relative.populated_fields(),
+        )),
     }
 }
 
@@ -389,32 +444,12 @@ fn render_time_range_absolute_start_absolute_end(
     start: AbsoluteTime,
     end: AbsoluteTime,
 ) -> Result<RenderedSpec> {
-    match (start, end) {
-        (
-            AbsoluteTime {
-                hour: Some(start_hour),
-                minute: start_minute,
-                meridiem: start_meridiem,
-            },
-            AbsoluteTime {
-                hour: Some(end_hour),
-                minute: end_minute,
-                meridiem: end_meridiem,
-            },
-        ) => {
-            let start_time = NaiveTime::from_hms(
-                apply_meridiem(start_hour, start_meridiem),
-                start_minute.unwrap_or(0),
-                0,
-            );
-            let end_time = NaiveTime::from_hms(
-                apply_meridiem(end_hour, end_meridiem),
-                end_minute.unwrap_or(0),
-                0,
-            );
-            Ok(RenderedSpec::TimeRange(start_time, end_time))
-        }
-        _ => unreachable!(),
+    match (start_time(&start)?, end_time(&end)?) {
+        (Some(start_time), Some(end_time)) => Ok(RenderedSpec::TimeRange(start_time, end_time)),
+        _ => Err(unsupported_range(
+            start.populated_fields(),
+            end.populated_fields(),
+        )),
     }
 }
 
@@ -422,29 +457,211 @@ fn render_time_range_absolute_start_relative_end(
     start: AbsoluteTime,
     end: RelativeTime,
 ) -> Result<RenderedSpec> {
-    match (start, end) {
+    match (start_time(&start)?, &end) {
         (
-            AbsoluteTime {
-                hour: Some(start_hour),
-                minute: start_minute,
-                meridiem: start_meridiem,
-            },
+            Some(start_time),
             RelativeTime {
                 hours: Some(end_hours),
                 minutes: end_minutes,
             },
         ) => {
-            let start_time = NaiveTime::from_hms(
-                apply_meridiem(start_hour, start_meridiem),
-                start_minute.unwrap_or(0),
-                0,
-            );
+            // Without a date, a day or more would come back round to a time it can't be told
+            // apart from
+            if *end_hours >= 24 {
+                return Err(OrgModeDateTimeError::FieldOutOfRange {
+                    field: Field::DurationHours,
+                    value: *end_hours,
+                });
+            }
             let end_time = start_time
-                + Duration::hours(end_hours.into())
+                + Duration::hours((*end_hours).into())
                 + Duration::minutes(end_minutes.unwrap_or(0).into());
             Ok(RenderedSpec::TimeRange(start_time, end_time))
         }
-        _ => unreachable!(),
+        _ => Err(unsupported_range(
+            start.populated_fields(),
+            end.populated_fields(),
+        )),
+    }
+}
+
+fn render_absolute_start(
+    start: Absolute,
+    end: Vec<Field>,
+    baseline: NaiveDateTime,
+    options: &RenderOptions,
+) -> Result<NaiveDateTime> {
+    match render_absolute(start.clone(), baseline, options)? {
+        RenderedSpec::DateTime(datetime) => Ok(datetime),
+        _ => Err(unsupported_range(start.populated_fields(), end)),
+    }
+}
+
+fn render_date_time_range_absolute_start_absolute_end(
+    start: Absolute,
+    end: AbsoluteTime,
+    baseline: NaiveDateTime,
+    options: &RenderOptions,
+) -> Result<RenderedSpec> {
+    let start_fields = start.populated_fields();
+    let start = render_absolute_start(start, end.populated_fields(), baseline, options)?;
+    let end_time =
+        end_time(&end)?.ok_or_else(|| unsupported_range(start_fields, end.populated_fields()))?;
+    Ok(RenderedSpec::DateTimeRange(start, end_at(start, end_time)?))
+}
+
+// "10pm-1am" ends on the following day
+fn end_at(start: NaiveDateTime, end_time: NaiveTime) -> Result<NaiveDateTime> {
+    let end = start.date().and_time(end_time);
+    if end < start {
+        end.checked_add_signed(Duration::days(1))
+            .ok_or(OrgModeDateTimeError::DateOutOfRange)
+    } else {
+        Ok(end)
+    }
+}
+
+fn end_after(start: NaiveDateTime, duration: &RelativeTime) -> Result<NaiveDateTime> {
+    start
+        .checked_add_signed(Duration::hours(duration.hours.unwrap_or(0).into()))
+        .and_then(|datetime| {
+            datetime.checked_add_signed(Duration::minutes(duration.minutes.unwrap_or(0).into()))
+        })
+        .ok_or(OrgModeDateTimeError::DateOutOfRange)
+}
+
+fn render_date_time_range_absolute_start_relative_end(
+    start: Absolute,
+    end: RelativeTime,
+    baseline: NaiveDateTime,
+    options: &RenderOptions,
+) -> Result<RenderedSpec> {
+    let start = render_absolute_start(start, end.populated_fields(), baseline, options)?;
+    Ok(RenderedSpec::DateTimeRange(start, end_after(start, &end)?))
+}
+
+// "tomorrow 3pm": the relative part has to come out as a date for the time to go on
+fn render_relative_with_time(
+    rendered: RenderedSpec,
+    relative: &Relative,
+    trailer: TimeTrailer,
+) -> Result<RenderedSpec> {
+    let unsupported = || {
+        let mut fields = relative.populated_fields();
+        fields.extend(trailer.populated_fields());
+        OrgModeDateTimeError::UnsupportedFields(fields)
+    };
+    let date = match rendered {
+        RenderedSpec::Date(date) => date,
+        _ => return Err(unsupported()),
+    };
+    let start = match &trailer {
+        TimeTrailer::Time(start)
+        | TimeTrailer::RangeAbsoluteEnd(start, _)
+        | TimeTrailer::RangeRelativeEnd(start, _) => start_time(start)?,
+    };
+    let start = date.and_time(start.ok_or_else(unsupported)?);
+    match &trailer {
+        TimeTrailer::Time(_) => Ok(RenderedSpec::DateTime(start)),
+        TimeTrailer::RangeAbsoluteEnd(_, end) => {
+            let end_time = end_time(end)?.ok_or_else(unsupported)?;
+            Ok(RenderedSpec::DateTimeRange(start, end_at(start, end_time)?))
+        }
+        TimeTrailer::RangeRelativeEnd(_, duration) => Ok(RenderedSpec::DateTimeRange(
+            start,
+            end_after(start, duration)?,
+        )),
+    }
+}
+
+// Everything rendering depends on besides the spec: the moment "+3d" and incomplete dates are
+// relative to, the base "++3d" is relative to (Org's default date, which starts out as now),
+// and the options.  Times are wall-clock times, so a zone written after one is ignored here;
+// ZonedRenderer converts it.
+//
+//     let renderer = Renderer::new(now).with_default(default).prefer(DatePreference::Past);
+//     let rendered = renderer.render(spec)?;
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Renderer {
+    now: NaiveDateTime,
+    default: NaiveDateTime,
+    options: RenderOptions,
+}
+
+impl Renderer {
+    pub fn new(now: NaiveDateTime) -> Renderer {
+        Renderer {
+            now,
+            default: now,
+            options: RenderOptions::default(),
+        }
+    }
+
+    pub fn with_default(mut self, default: NaiveDateTime) -> Renderer {
+        self.default = default;
+        self
+    }
+
+    pub fn with_options(mut self, options: RenderOptions) -> Renderer {
+        self.options = options;
+        self
+    }
+
+    pub fn prefer(mut self, prefer: DatePreference) -> Renderer {
+        self.options.prefer = prefer;
+        self
+    }
+
+    pub fn now(&self) -> NaiveDateTime {
+        self.now
+    }
+
+    pub fn default_date(&self) -> NaiveDateTime {
+        self.default
+    }
+
+    pub fn options(&self) -> &RenderOptions {
+        &self.options
+    }
+
+    pub fn render(&self, spec: DateTimeSpec) -> Result<RenderedSpec> {
+        let (now, default, options) = (self.now, self.default, &self.options);
+        spec.validate()?;
+        match spec {
+            DateTimeSpec::Absolute(absolute) => render_absolute(absolute, now, options),
+            DateTimeSpec::NowRelativeFuture(relative) => {
+                render_relative_future(relative, now, MonthEnd::Error)
+            }
+            DateTimeSpec::NowRelativePast(relative) => {
+                render_relative_past(relative, now, MonthEnd::Error)
+            }
+            DateTimeSpec::NowRelativeFutureWithTime(relative, trailer) => {
+                let date = render_relative_future(relative.clone(), now, MonthEnd::Error)?;
+                render_relative_with_time(date, &relative, trailer)
+            }
+            DateTimeSpec::NowRelativePastWithTime(relative, trailer) => {
+                let date = render_relative_past(relative.clone(), now, MonthEnd::Error)?;
+                render_relative_with_time(date, &relative, trailer)
+            }
+            DateTimeSpec::DefaultRelativeFuture(relative) => {
+                render_relative_future(relative, default, MonthEnd::Error)
+            }
+            DateTimeSpec::DefaultRelativePast(relative) => {
+                render_relative_past(relative, default, MonthEnd::Error)
+            }
+            DateTimeSpec::TimeRangeAbsoluteStartAbsoluteEnd(start, end) => {
+                render_time_range_absolute_start_absolute_end(start, end)
+            }
+            DateTimeSpec::TimeRangeAbsoluteStartRelativeEnd(start, end) => {
+                render_time_range_absolute_start_relative_end(start, end)
+            }
+            DateTimeSpec::DateTimeRangeAbsoluteStartAbsoluteEnd(start, end) => {
+                render_date_time_range_absolute_start_absolute_end(start, end, now, options)
+            }
+            DateTimeSpec::DateTimeRangeAbsoluteStartRelativeEnd(start, end) => {
+                render_date_time_range_absolute_start_relative_end(start, end, now, options)
+            }
+        }
     }
 }
 
@@ -453,17 +670,440 @@ pub fn render(
     now: NaiveDateTime,
     spec: DateTimeSpec,
 ) -> Result<RenderedSpec> {
-    match spec {
-        DateTimeSpec::Absolute(absolute) => render_absolute(absolute, now),
-        DateTimeSpec::NowRelativeFuture(relative) => render_relative_future(relative, now),
-        DateTimeSpec::NowRelativePast(relative) => render_relative_past(relative, now),
-        DateTimeSpec::DefaultRelativeFuture(relative) => render_relative_future(relative, default),
-        DateTimeSpec::DefaultRelativePast(relative) => render_relative_past(relative, default),
-        DateTimeSpec::TimeRangeAbsoluteStartAbsoluteEnd(start, end) => {
-            render_time_range_absolute_start_absolute_end(start, end)
+    Renderer::new(now).with_default(default).render(spec)
+}
+
+pub fn render_with_options(
+    default: NaiveDateTime,
+    now: NaiveDateTime,
+    spec: DateTimeSpec,
+    options: &RenderOptions,
+) -> Result<RenderedSpec> {
+    Renderer::new(now)
+        .with_default(default)
+        .with_options(options.clone())
+        .render(spec)
+}
+
+#[cfg(test)]
+mod test_render {
+    use chrono::Timelike;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::parser::parse_strict;
+    use crate::parser::Zone;
+
+    fn at(year: i32, month: u32, day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    }
+
+    fn rendered(input: &str, now: NaiveDateTime) -> Result<RenderedSpec> {
+        render(now, now, parse_strict(input).unwrap())
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> RenderedSpec {
+        RenderedSpec::Date(NaiveDate::from_ymd_opt(year, month, day).unwrap())
+    }
+
+    #[test]
+    fn test_day_missing_from_month() {
+        assert_eq!(rendered("31", at(2006, 6, 13)).unwrap(), date(2006, 7, 31));
+        assert_eq!(rendered("5", at(2006, 12, 20)).unwrap(), date(2007, 1, 5));
+        assert_eq!(
+            rendered("feb 29", at(2006, 3, 1)).unwrap(),
+            date(2008, 2, 29)
+        );
+        assert!(matches!(
+            rendered("3-2-29", at(2006, 6, 13)),
+            Err(OrgModeDateTimeError::FieldOutOfRange {
+                field: Field::Day,
+                value: 29
+            })
+        ));
+    }
+
+    #[test]
+    fn test_month_end() {
+        let months = Relative {
+            months: Some(1),
+            ..Default::default()
+        };
+        assert!(matches!(
+            rendered("-1m", at(2006, 3, 31)),
+            Err(OrgModeDateTimeError::UnrepresentablePastRelativeDate(_))
+        ));
+        assert_eq!(
+            shift_by_relative(at(2006, 3, 31), &months, -1, MonthEnd::Clamp),
+            Some(at(2006, 2, 28))
+        );
+        assert_eq!(
+            shift_by_relative(at(2004, 1, 30), &months, 1, MonthEnd::Clamp),
+            Some(at(2004, 2, 29))
+        );
+        assert_eq!(
+            shift_by_relative(at(2006, 3, 15), &months, 1, MonthEnd::Clamp),
+            Some(at(2006, 4, 15))
+        );
+    }
+
+    #[test]
+    fn test_twelve_oclock() {
+        assert_eq!(
+            rendered("2006-6-13 12pm", at(2006, 6, 13)).unwrap(),
+            RenderedSpec::DateTime(at(2006, 6, 13).with_hour(12).unwrap())
+        );
+        assert_eq!(
+            rendered("2006-6-13 12am", at(2006, 6, 13)).unwrap(),
+            RenderedSpec::DateTime(at(2006, 6, 13))
+        );
+    }
+
+    #[test]
+    fn test_keyword_with_time() {
+        let now = at(2006, 6, 13).with_hour(18).unwrap();
+        assert_eq!(
+            rendered("tomorrow noon", now).unwrap(),
+            RenderedSpec::DateTime(at(2006, 6, 14).with_hour(12).unwrap())
+        );
+        // Earlier today, unlike a bare "3pm"
+        assert_eq!(
+            rendered("today 3pm", now).unwrap(),
+            RenderedSpec::DateTime(at(2006, 6, 13).with_hour(15).unwrap())
+        );
+        assert_eq!(
+            rendered("yesterday 10pm-1am", now).unwrap(),
+            RenderedSpec::DateTimeRange(
+                at(2006, 6, 12).with_hour(22).unwrap(),
+                at(2006, 6, 13).with_hour(1).unwrap()
+            )
+        );
+        assert_eq!(
+            rendered("+2h 3pm", now).unwrap_err().to_string(),
+            "Unsupported combination of fields: hours, hour, meridiem"
+        );
+    }
+
+    #[test]
+    fn test_unsupported_fields() {
+        let spec = DateTimeSpec::Absolute(Absolute {
+            month: Some(6),
+            minute: Some(30),
+            ..Default::default()
+        });
+        let now = at(2006, 6, 13);
+        let err = render(now, now, spec).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unsupported combination of fields: month, minute"
+        );
+        let spec = DateTimeSpec::NowRelativeFuture(Relative {
+            days: Some(1),
+            weekdays: Some(5),
+            ..Default::default()
+        });
+        assert!(matches!(
+            render(now, now, spec),
+            Err(OrgModeDateTimeError::UnsupportedFields(fields))
+                if fields == vec![Field::Days, Field::Weekdays]
+        ));
+    }
+
+    #[test]
+    fn test_undated_duration() {
+        let now = at(2006, 6, 13);
+        assert_eq!(
+            rendered("10pm+23:59", now).unwrap(),
+            RenderedSpec::TimeRange(
+                NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+                NaiveTime::from_hms_opt(21, 59, 0).unwrap()
+            )
+        );
+        for input in ["9:00+24:00", "9:00+4294967295:00"] {
+            assert!(
+                matches!(
+                    rendered(input, now),
+                    Err(OrgModeDateTimeError::FieldOutOfRange {
+                        field: Field::DurationHours,
+                        ..
+                    })
+                ),
+                "{:?} should be rejected",
+                input
+            );
         }
-        DateTimeSpec::TimeRangeAbsoluteStartRelativeEnd(start, end) => {
-            render_time_range_absolute_start_relative_end(start, end)
+        assert!(rendered("fri 9:00+24:00", now).is_ok());
+    }
+
+    #[test]
+    fn test_end_of_calendar() {
+        let last = NaiveDate::MAX.and_hms_opt(0, 0, 0).unwrap();
+        assert!(matches!(
+            rendered("+1d", last),
+            Err(OrgModeDateTimeError::UnrepresentableFutureRelativeDate(_))
+        ));
+        assert!(matches!(
+            rendered("+fri", last),
+            Err(OrgModeDateTimeError::UnrepresentableFutureRelativeDate(_))
+        ));
+        assert!(matches!(
+            rendered("5", last),
+            Err(OrgModeDateTimeError::DateOutOfRange)
+        ));
+    }
+
+    #[test]
+    fn test_renderer_bases() {
+        let now = at(2006, 6, 13);
+        let default = at(2006, 1, 1);
+        let renderer = Renderer::new(now).with_default(default);
+        assert_eq!(
+            renderer.render(parse_strict("+3d").unwrap()).unwrap(),
+            date(2006, 6, 16)
+        );
+        assert_eq!(
+            renderer.render(parse_strict("++3d").unwrap()).unwrap(),
+            date(2006, 1, 4)
+        );
+        assert_eq!(
+            Renderer::new(now)
+                .render(parse_strict("++3d").unwrap())
+                .unwrap(),
+            date(2006, 6, 16)
+        );
+        assert_eq!(renderer.options(), &RenderOptions::default());
+        let renderer = renderer.with_options(RenderOptions {
+            prefer: DatePreference::Past,
+            ..Default::default()
+        });
+        assert_eq!(
+            renderer.render(parse_strict("12").unwrap()).unwrap(),
+            date(2006, 6, 12)
+        );
+    }
+
+    fn preferring(input: &str, prefer: DatePreference) -> RenderedSpec {
+        // Saturday noon
+        let now = at(2006, 6, 17).with_hour(12).unwrap();
+        Renderer::new(now)
+            .prefer(prefer)
+            .render(parse_strict(input).unwrap())
+            .unwrap()
+    }
+
+    fn date_time(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        at(year, month, day)
+            .with_hour(hour)
+            .unwrap()
+            .with_minute(minute)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_prefer_past() {
+        let prefer = DatePreference::Past;
+        assert_eq!(preferring("fri", prefer), date(2006, 6, 16));
+        assert_eq!(preferring("sat", prefer), date(2006, 6, 17));
+        assert_eq!(preferring("sep 15", prefer), date(2005, 9, 15));
+        assert_eq!(preferring("12", prefer), date(2006, 6, 12));
+        assert_eq!(preferring("31", prefer), date(2006, 5, 31));
+        assert_eq!(
+            preferring("12:45", prefer),
+            RenderedSpec::DateTime(date_time(2006, 6, 16, 12, 45))
+        );
+        assert_eq!(
+            preferring("fri 9:00-10:00", prefer),
+            RenderedSpec::DateTimeRange(
+                date_time(2006, 6, 16, 9, 0),
+                date_time(2006, 6, 16, 10, 0)
+            )
+        );
+        // Complete dates stay put
+        assert_eq!(preferring("2007-1-1", prefer), date(2007, 1, 1));
+    }
+
+    #[test]
+    fn test_prefer_future() {
+        let prefer = DatePreference::Future;
+        assert_eq!(preferring("fri", prefer), date(2006, 6, 23));
+        assert_eq!(preferring("sat", prefer), date(2006, 6, 17));
+        assert_eq!(preferring("may 1", prefer), date(2007, 5, 1));
+        assert_eq!(preferring("12", prefer), date(2006, 7, 12));
+        assert_eq!(
+            preferring("11:00", prefer),
+            RenderedSpec::DateTime(date_time(2006, 6, 18, 11, 0))
+        );
+    }
+
+    #[test]
+    fn test_prefer_nearest() {
+        let prefer = DatePreference::Nearest;
+        assert_eq!(preferring("fri", prefer), date(2006, 6, 16));
+        assert_eq!(preferring("mon", prefer), date(2006, 6, 19));
+        assert_eq!(preferring("sep 15", prefer), date(2006, 9, 15));
+        assert_eq!(preferring("jan 2", prefer), date(2006, 1, 2));
+        assert_eq!(preferring("12", prefer), date(2006, 6, 12));
+        assert_eq!(preferring("1", prefer), date(2006, 7, 1));
+        assert_eq!(
+            preferring("11:00", prefer),
+            RenderedSpec::DateTime(date_time(2006, 6, 17, 11, 0))
+        );
+        // Twelve hours either way
+        assert_eq!(
+            preferring("0:00", prefer),
+            RenderedSpec::DateTime(date_time(2006, 6, 18, 0, 0))
+        );
+    }
+
+    // Mostly values near the valid ranges, with the odd extreme one
+    fn number() -> impl Strategy<Value = u32> {
+        prop_oneof![8 => 0..70u32, 1 => any::<u32>()]
+    }
+
+    fn field() -> impl Strategy<Value = Option<u32>> {
+        proptest::option::of(number())
+    }
+
+    fn meridiem() -> impl Strategy<Value = Option<Meridiem>> {
+        proptest::option::of(prop_oneof![Just(Meridiem::AM), Just(Meridiem::PM)])
+    }
+
+    // The renderer ignores zones, but they ride along
+    fn zone() -> impl Strategy<Value = Option<Zone>> {
+        proptest::option::of(prop_oneof![
+            any::<i32>().prop_map(Zone::Offset),
+            Just(Zone::Named(chrono_tz::Europe::Warsaw)),
+        ])
+    }
+
+    fn absolute() -> BoxedStrategy<Absolute> {
+        (
+            field(),
+            field(),
+            field(),
+            field(),
+            field(),
+            field(),
+            field(),
+            meridiem(),
+            zone(),
+        )
+            .prop_map(
+                |(year, month, day, week, weekday, hour, minute, meridiem, zone)| Absolute {
+                    year,
+                    month,
+                    day,
+                    week,
+                    weekday,
+                    hour,
+                    minute,
+                    meridiem,
+                    zone,
+                },
+            )
+            .boxed()
+    }
+
+    fn absolute_time() -> BoxedStrategy<AbsoluteTime> {
+        (field(), field(), meridiem(), zone())
+            .prop_map(|(hour, minute, meridiem, zone)| AbsoluteTime {
+                hour,
+                minute,
+                meridiem,
+                zone,
+            })
+            .boxed()
+    }
+
+    fn relative() -> BoxedStrategy<Relative> {
+        (field(), field(), field(), field(), field(), field())
+            .prop_map(|(hours, days, weeks, weekdays, months, years)| Relative {
+                hours,
+                days,
+                weeks,
+                weekdays,
+                months,
+                years,
+            })
+            .boxed()
+    }
+
+    fn relative_time() -> BoxedStrategy<RelativeTime> {
+        (field(), field())
+            .prop_map(|(hours, minutes)| RelativeTime { hours, minutes })
+            .boxed()
+    }
+
+    fn time_trailer() -> BoxedStrategy<TimeTrailer> {
+        prop_oneof![
+            absolute_time().prop_map(TimeTrailer::Time),
+            (absolute_time(), absolute_time())
+                .prop_map(|(start, end)| TimeTrailer::RangeAbsoluteEnd(start, end)),
+            (absolute_time(), relative_time())
+                .prop_map(|(start, end)| TimeTrailer::RangeRelativeEnd(start, end)),
+        ]
+        .boxed()
+    }
+
+    fn date_time_spec() -> BoxedStrategy<DateTimeSpec> {
+        prop_oneof![
+            absolute().prop_map(DateTimeSpec::Absolute),
+            relative().prop_map(DateTimeSpec::NowRelativeFuture),
+            relative().prop_map(DateTimeSpec::NowRelativePast),
+            (relative(), time_trailer()).prop_map(|(relative, trailer)| {
+                DateTimeSpec::NowRelativeFutureWithTime(relative, trailer)
+            }),
+            (relative(), time_trailer()).prop_map(|(relative, trailer)| {
+                DateTimeSpec::NowRelativePastWithTime(relative, trailer)
+            }),
+            relative().prop_map(DateTimeSpec::DefaultRelativeFuture),
+            relative().prop_map(DateTimeSpec::DefaultRelativePast),
+            (absolute_time(), absolute_time()).prop_map(|(start, end)| {
+                DateTimeSpec::TimeRangeAbsoluteStartAbsoluteEnd(start, end)
+            }),
+            (absolute_time(), relative_time()).prop_map(|(start, end)| {
+                DateTimeSpec::TimeRangeAbsoluteStartRelativeEnd(start, end)
+            }),
+            (absolute(), absolute_time()).prop_map(|(start, end)| {
+                DateTimeSpec::DateTimeRangeAbsoluteStartAbsoluteEnd(start, end)
+            }),
+            (absolute(), relative_time()).prop_map(|(start, end)| {
+                DateTimeSpec::DateTimeRangeAbsoluteStartRelativeEnd(start, end)
+            }),
+        ]
+        .boxed()
+    }
+
+    fn baseline() -> impl Strategy<Value = NaiveDateTime> {
+        let first = NaiveDate::MIN.num_days_from_ce();
+        let last = NaiveDate::MAX.num_days_from_ce();
+        let day = prop_oneof![
+            Just(first),
+            Just(last),
+            (first..=last),
+            // Around the present, where abbreviated years land
+            (730_000..740_000),
+        ];
+        (day, 0..86_400u32).prop_map(|(day, seconds)| {
+            NaiveDate::from_num_days_from_ce_opt(day)
+                .unwrap()
+                .and_time(NaiveTime::from_num_seconds_from_midnight_opt(seconds, 0).unwrap())
+        })
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(2000))]
+
+        #[test]
+        fn test_render_never_panics(
+            spec in date_time_spec(),
+            default in baseline(),
+            now in baseline(),
+        ) {
+            let _ = render(default, now, spec);
         }
     }
 }
@@ -0,0 +1,327 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::ops::Range;
+
+use crate::error::Result;
+use crate::locale::Locale;
+use crate::parse_error::PResult;
+use crate::parser::{english, parse_str_with_locale, DateTimeSpec};
+
+// A populated field of the spec parse_located() returns
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Field {
+    // Absolute, or the start of a range
+    Year,
+    Month,
+    Day,
+    Week,
+    Weekday,
+    Hour,
+    Minute,
+    Meridiem,
+    Zone,
+    // The AbsoluteTime ending a range
+    EndHour,
+    EndMinute,
+    EndMeridiem,
+    EndZone,
+    // Relative
+    Hours,
+    Days,
+    Weeks,
+    Weekdays,
+    Months,
+    Years,
+    // The RelativeTime ending a range
+    DurationHours,
+    DurationMinutes,
+}
+
+impl Field {
+    pub(crate) fn as_range_end(self) -> Field {
+        match self {
+            Field::Hour => Field::EndHour,
+            Field::Minute => Field::EndMinute,
+            Field::Meridiem => Field::EndMeridiem,
+            Field::Zone => Field::EndZone,
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Field::Year => "year",
+            Field::Month => "month",
+            Field::Day => "day",
+            Field::Week => "week",
+            Field::Weekday => "weekday",
+            Field::Hour => "hour",
+            Field::Minute => "minute",
+            Field::Meridiem => "meridiem",
+            Field::Zone => "zone",
+            Field::EndHour => "end hour",
+            Field::EndMinute => "end minute",
+            Field::EndMeridiem => "end meridiem",
+            Field::EndZone => "end zone",
+            Field::Hours => "hours",
+            Field::Days => "days",
+            Field::Weeks => "weeks",
+            Field::Weekdays => "weekdays",
+            Field::Months => "months",
+            Field::Years => "years",
+            Field::DurationHours => "duration hours",
+            Field::DurationMinutes => "duration minutes",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FieldSpan {
+    pub field: Field,
+    // Byte offsets into the input
+    pub range: Range<usize>,
+}
+
+// A field recorded while parsing, located by the length of the input left at its start and end
+struct Recorded {
+    field: Field,
+    start: usize,
+    end: usize,
+}
+
+thread_local! {
+    // Fields parsed so far, while record_spans() runs
+    static SPANS: RefCell<Option<Vec<Recorded>>> = const { RefCell::new(None) };
+}
+
+fn with_spans(f: impl FnOnce(&mut Vec<Recorded>)) {
+    SPANS.with(|spans| {
+        if let Some(spans) = spans.borrow_mut().as_mut() {
+            f(spans)
+        }
+    });
+}
+
+// The successful parse reads its fields left to right without overlaps, so anything reaching
+// past the start of new fields was left behind by an alternative that didn't pan out
+fn note_spans(fields: &[Field], start: usize, end: usize) {
+    with_spans(|spans| {
+        spans.retain(|span| span.end >= start);
+        for &field in fields {
+            spans.push(Recorded { field, start, end });
+        }
+    });
+}
+
+// Records what `parser` consumes as `field`
+pub(crate) fn field<'a, T>(
+    field: Field,
+    parser: impl Fn(&'a str) -> PResult<'a, T>,
+) -> impl Fn(&'a str) -> PResult<'a, T> {
+    move |input| {
+        let (rest, value) = parser(input)?;
+        note_spans(&[field], input.len(), rest.len());
+        Ok((rest, value))
+    }
+}
+
+// Records what `parser` consumes as all of `fields`, like "noon" for both the hour and minute
+pub(crate) fn fields<'a, T>(
+    fields: &'static [Field],
+    parser: impl Fn(&'a str) -> PResult<'a, T>,
+) -> impl Fn(&'a str) -> PResult<'a, T> {
+    move |input| {
+        let (rest, value) = parser(input)?;
+        note_spans(fields, input.len(), rest.len());
+        Ok((rest, value))
+    }
+}
+
+// Changes the fields recorded from `input` on, for parsers whose fields mean something else
+// than usual depending on where they appear
+pub(crate) fn relabel_from(input: &str, relabel: impl Fn(Field) -> Field) {
+    with_spans(|spans| {
+        for span in spans.iter_mut().filter(|span| span.start <= input.len()) {
+            span.field = relabel(span.field);
+        }
+    });
+}
+
+// Records the time `parser` consumes as the end of a range
+pub(crate) fn range_end<'a, T>(
+    parser: impl Fn(&'a str) -> PResult<'a, T>,
+) -> impl Fn(&'a str) -> PResult<'a, T> {
+    move |input| {
+        let (rest, value) = parser(input)?;
+        relabel_from(input, Field::as_range_end);
+        Ok((rest, value))
+    }
+}
+
+fn record_spans<T>(f: impl FnOnce() -> T) -> (T, Vec<Recorded>) {
+    SPANS.with(|spans| *spans.borrow_mut() = Some(Vec::new()));
+    let result = f();
+    let spans = SPANS.with(|spans| spans.borrow_mut().take());
+    (result, spans.unwrap_or_default())
+}
+
+// Like parse_str_with_locale, but also returns where each populated field of the spec came
+// from, in input order
+pub fn parse_located_with_locale<'a>(
+    input: &'a str,
+    locale: &Locale,
+) -> Result<(&'a str, DateTimeSpec, Vec<FieldSpan>)> {
+    let (result, spans) = record_spans(|| parse_str_with_locale(input, locale));
+    let (rest, spec) = result?;
+    let spans = spans
+        .into_iter()
+        // Whatever an optional trailer parsed before giving up
+        .filter(|span| span.end >= rest.len())
+        .map(|span| FieldSpan {
+            field: span.field,
+            range: input.len() - span.start..input.len() - span.end,
+        })
+        .collect();
+    Ok((rest, spec, spans))
+}
+
+pub fn parse_located(input: &str) -> Result<(&str, DateTimeSpec, Vec<FieldSpan>)> {
+    parse_located_with_locale(input, english())
+}
+
+#[cfg(test)]
+mod test_parse_located {
+    use super::*;
+
+    fn spans(input: &str) -> Vec<(Field, &str)> {
+        let (_, _, spans) = parse_located(input).unwrap();
+        spans
+            .into_iter()
+            .map(|span| (span.field, &input[span.range]))
+            .collect()
+    }
+
+    #[test]
+    fn test_dashed_date_time() {
+        assert_eq!(
+            spans("2006-06-13 14:30"),
+            vec![
+                (Field::Year, "2006"),
+                (Field::Month, "06"),
+                (Field::Day, "13"),
+                (Field::Hour, "14"),
+                (Field::Minute, "30"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_month_name_day() {
+        assert_eq!(
+            spans("sep 15 9:30"),
+            vec![
+                (Field::Month, "sep"),
+                (Field::Day, "15"),
+                (Field::Hour, "9"),
+                (Field::Minute, "30"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_range_end() {
+        assert_eq!(
+            spans("fri 11am-1:30pm"),
+            vec![
+                (Field::Weekday, "fri"),
+                (Field::Hour, "11"),
+                (Field::Meridiem, "am"),
+                (Field::EndHour, "1"),
+                (Field::EndMinute, "30"),
+                (Field::EndMeridiem, "pm"),
+            ]
+        );
+        assert_eq!(
+            spans("9:00+1:30"),
+            vec![
+                (Field::Hour, "9"),
+                (Field::Minute, "00"),
+                (Field::DurationHours, "1"),
+                (Field::DurationMinutes, "30"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_zone() {
+        assert_eq!(
+            spans("9am +02:00"),
+            vec![
+                (Field::Hour, "9"),
+                (Field::Meridiem, "am"),
+                (Field::Zone, "+02:00"),
+            ]
+        );
+        assert_eq!(
+            spans("9:00-10:00 Europe/Warsaw"),
+            vec![
+                (Field::Hour, "9"),
+                (Field::Minute, "00"),
+                (Field::EndHour, "10"),
+                (Field::EndMinute, "00"),
+                (Field::Zone, "Europe/Warsaw"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_relative() {
+        // The first alternative reads "3" as a number of weeks before failing on "d"
+        assert_eq!(spans("+3d"), vec![(Field::Days, "3d")]);
+        assert_eq!(spans("tomorrow"), vec![(Field::Days, "tomorrow")]);
+        assert_eq!(
+            spans("-2fri"),
+            vec![(Field::Weeks, "2"), (Field::Weekdays, "fri")]
+        );
+    }
+
+    #[test]
+    fn test_noon() {
+        assert_eq!(
+            spans("fri noon"),
+            vec![
+                (Field::Weekday, "fri"),
+                (Field::Hour, "noon"),
+                (Field::Minute, "noon"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_iso_week() {
+        assert_eq!(
+            spans("2012-w04-5"),
+            vec![
+                (Field::Year, "2012"),
+                (Field::Week, "w04"),
+                (Field::Weekday, "5"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unused_trailer_left_out() {
+        let (rest, _, spans) = parse_located("fri 9:x").unwrap();
+        assert_eq!(rest, " 9:x");
+        assert_eq!(
+            spans,
+            vec![FieldSpan {
+                field: Field::Weekday,
+                range: 0..3
+            }]
+        );
+    }
+}
@@ -0,0 +1,989 @@
+use std::fmt;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use nom::bytes::complete::{is_not, tag};
+use nom::character::complete::space1;
+use nom::combinator::{map, map_opt, opt, verify};
+use nom::sequence::preceded;
+use nom::{branch::alt, IResult};
+
+use crate::error::{OrgModeDateTimeError, Result};
+use crate::parse_error::PResult;
+use crate::parser::{number, wall_time, Relative};
+use crate::renderer::{
+    apply_meridiem, render_relative_past, shift_by_relative, MonthEnd, RenderedSpec,
+};
+
+pub const ENGLISH_WEEKDAY_ABBREVIATIONS: [&str; 7] =
+    ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TimestampKind {
+    // <...>, shows up in the agenda
+    Active,
+    // [...]
+    Inactive,
+}
+
+// A timestamp as Org writes it into files, e.g. <2006-06-13 Tue 10:00-12:00>.  The weekday
+// name is accepted on input but not stored: it's always derived from `date`.
+// https://orgmode.org/manual/Timestamps.html
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Timestamp {
+    pub kind: TimestampKind,
+    pub date: NaiveDate,
+    pub start_time: Option<NaiveTime>,
+    pub end_time: Option<NaiveTime>,
+    pub repeater: Option<Repeater>,
+    pub delay: Option<Delay>,
+}
+
+// Two timestamps joined by "--", e.g. <2006-06-13 Tue>--<2006-06-15 Thu>
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TimestampRange {
+    pub start: Timestamp,
+    pub end: Timestamp,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TimeUnit {
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+// A count of time units, e.g. the "2d" in ".+2d"
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Interval {
+    pub count: u32,
+    pub unit: TimeUnit,
+}
+
+// https://orgmode.org/manual/Repeated-tasks.html
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RepeaterKind {
+    // +1w: shift by the interval once
+    Cumulate,
+    // ++1w: shift by the interval until the date is in the future
+    CatchUp,
+    // .+1w: shift by the interval counting from today
+    Restart,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Repeater {
+    pub kind: RepeaterKind,
+    pub interval: Interval,
+    // The "4d" in a habit's ".+2d/4d"
+    pub habit_maximum: Option<Interval>,
+}
+
+// https://orgmode.org/manual/Deadlines-and-Scheduling.html
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DelayKind {
+    // -3d: applies to every occurrence
+    All,
+    // --2d: applies to the first occurrence of a repeated timestamp only
+    FirstOccurrence,
+}
+
+// A warning (on DEADLINE) or delay (on SCHEDULED) cookie
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Delay {
+    pub kind: DelayKind,
+    pub interval: Interval,
+}
+
+fn year_month_day(input: &str) -> PResult<'_, (u32, u32, u32)> {
+    let (input, year) = number(input)?;
+    let (input, _) = tag("-")(input)?;
+    let (input, month) = number(input)?;
+    let (input, _) = tag("-")(input)?;
+    let (input, day) = number(input)?;
+    Ok((input, (year, month, day)))
+}
+
+fn date(input: &str) -> PResult<'_, NaiveDate> {
+    map_opt(year_month_day, |(year, month, day)| {
+        NaiveDate::from_ymd_opt(i32::try_from(year).ok()?, month, day)
+    })(input)
+}
+
+// Org doesn't check the day name against the date and accepts any word there, so localized names
+// ("Di", "mar.") parse just as well as English ones
+fn day_name(input: &str) -> PResult<'_, &str> {
+    is_not("]+0123456789>\r\n -")(input)
+}
+
+fn time(input: &str) -> PResult<'_, NaiveTime> {
+    map_opt(wall_time, |time| {
+        NaiveTime::from_hms_opt(
+            apply_meridiem(time.hour?, time.meridiem),
+            time.minute.unwrap_or(0),
+            0,
+        )
+    })(input)
+}
+
+fn time_range(input: &str) -> PResult<'_, (NaiveTime, Option<NaiveTime>)> {
+    let (input, start) = time(input)?;
+    let (input, end) = opt(preceded(tag("-"), time))(input)?;
+    Ok((input, (start, end)))
+}
+
+fn time_unit(input: &str) -> PResult<'_, TimeUnit> {
+    alt((
+        map(tag("h"), |_| TimeUnit::Hour),
+        map(tag("d"), |_| TimeUnit::Day),
+        map(tag("w"), |_| TimeUnit::Week),
+        map(tag("m"), |_| TimeUnit::Month),
+        map(tag("y"), |_| TimeUnit::Year),
+    ))(input)
+}
+
+fn interval(input: &str) -> PResult<'_, Interval> {
+    let (input, count) = number(input)?;
+    let (input, unit) = time_unit(input)?;
+    Ok((input, Interval { count, unit }))
+}
+
+fn repeater_kind(input: &str) -> PResult<'_, RepeaterKind> {
+    alt((
+        map(tag(".+"), |_| RepeaterKind::Restart),
+        map(tag("++"), |_| RepeaterKind::CatchUp),
+        map(tag("+"), |_| RepeaterKind::Cumulate),
+    ))(input)
+}
+
+fn repeater(input: &str) -> PResult<'_, Repeater> {
+    let (input, kind) = repeater_kind(input)?;
+    let (input, every) = interval(input)?;
+    let (input, habit_maximum) = opt(preceded(tag("/"), interval))(input)?;
+    Ok((
+        input,
+        Repeater {
+            kind,
+            interval: every,
+            habit_maximum,
+        },
+    ))
+}
+
+fn delay_kind(input: &str) -> PResult<'_, DelayKind> {
+    alt((
+        map(tag("--"), |_| DelayKind::FirstOccurrence),
+        map(tag("-"), |_| DelayKind::All),
+    ))(input)
+}
+
+fn delay(input: &str) -> PResult<'_, Delay> {
+    let (input, kind) = delay_kind(input)?;
+    let (input, interval) = interval(input)?;
+    Ok((input, Delay { kind, interval }))
+}
+
+// Everything between the brackets: 2006-06-13 Tue 10:00-12:00 +1w -3d
+fn timestamp_body(input: &str, kind: TimestampKind) -> PResult<'_, Timestamp> {
+    let (input, date) = date(input)?;
+    let (input, _) = opt(preceded(space1, day_name))(input)?;
+    let (input, times) = opt(preceded(space1, time_range))(input)?;
+    let (input, leading_repeater) = opt(preceded(space1, repeater))(input)?;
+    let (input, delay) = opt(preceded(space1, delay))(input)?;
+    // Org accepts the delay before the repeater too
+    let (input, repeater) = match leading_repeater {
+        Some(_) => (input, leading_repeater),
+        None => opt(preceded(space1, repeater))(input)?,
+    };
+    Ok((
+        input,
+        Timestamp {
+            kind,
+            date,
+            start_time: times.map(|(start, _)| start),
+            end_time: times.and_then(|(_, end)| end),
+            repeater,
+            delay,
+        },
+    ))
+}
+
+fn active_timestamp(input: &str) -> PResult<'_, Timestamp> {
+    let (input, _) = tag("<")(input)?;
+    let (input, timestamp) = timestamp_body(input, TimestampKind::Active)?;
+    let (input, _) = tag(">")(input)?;
+    Ok((input, timestamp))
+}
+
+fn inactive_timestamp(input: &str) -> PResult<'_, Timestamp> {
+    let (input, _) = tag("[")(input)?;
+    let (input, timestamp) = timestamp_body(input, TimestampKind::Inactive)?;
+    let (input, _) = tag("]")(input)?;
+    Ok((input, timestamp))
+}
+
+fn timestamp(input: &str) -> PResult<'_, Timestamp> {
+    alt((active_timestamp, inactive_timestamp))(input)
+}
+
+pub fn parse_timestamp(input: &str) -> IResult<&str, Timestamp> {
+    timestamp(input).map_err(|err| err.map(Into::into))
+}
+
+fn timestamp_pair(input: &str) -> PResult<'_, (Timestamp, Timestamp)> {
+    let (input, start) = timestamp(input)?;
+    let (input, _) = tag("--")(input)?;
+    let (input, end) = timestamp(input)?;
+    Ok((input, (start, end)))
+}
+
+fn timestamp_range(input: &str) -> PResult<'_, TimestampRange> {
+    // Both ends have to be either active or inactive
+    let (input, (start, end)) = verify(timestamp_pair, |(start, end): &(Timestamp, Timestamp)| {
+        start.kind == end.kind
+    })(input)?;
+    Ok((input, TimestampRange { start, end }))
+}
+
+pub fn parse_timestamp_range(input: &str) -> IResult<&str, TimestampRange> {
+    timestamp_range(input).map_err(|err| err.map(Into::into))
+}
+
+impl TimestampRange {
+    // The first instant covered by the range
+    pub fn start_datetime(&self) -> NaiveDateTime {
+        self.start
+            .date
+            .and_time(self.start.start_time.unwrap_or(NaiveTime::MIN))
+    }
+
+    // The instant the range ends at (exclusive).  Without a time, the range lasts until the end
+    // of its last day, which has no following midnight on NaiveDate::MAX.
+    pub fn end_datetime(&self) -> Option<NaiveDateTime> {
+        match self.end.end_time.or(self.end.start_time) {
+            Some(time) => Some(self.end.date.and_time(time)),
+            None => Some(self.end.date.succ_opt()?.and_time(NaiveTime::MIN)),
+        }
+    }
+
+    // Number of calendar days the range touches, counting both ends: 3 for
+    // <2006-06-13 Tue>--<2006-06-15 Thu>
+    pub fn days(&self) -> i64 {
+        (self.end.date - self.start.date).num_days() + 1
+    }
+
+    pub fn duration(&self) -> Option<Duration> {
+        Some(self.end_datetime()? - self.start_datetime())
+    }
+
+    pub fn contains(&self, datetime: NaiveDateTime) -> bool {
+        match self.end_datetime() {
+            Some(end) => self.start_datetime() <= datetime && datetime < end,
+            None => false,
+        }
+    }
+}
+
+impl fmt::Display for TimeUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unit = match self {
+            TimeUnit::Hour => "h",
+            TimeUnit::Day => "d",
+            TimeUnit::Week => "w",
+            TimeUnit::Month => "m",
+            TimeUnit::Year => "y",
+        };
+        write!(f, "{}", unit)
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.count, self.unit)
+    }
+}
+
+impl fmt::Display for Repeater {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            RepeaterKind::Cumulate => "+",
+            RepeaterKind::CatchUp => "++",
+            RepeaterKind::Restart => ".+",
+        };
+        write!(f, "{}{}", kind, self.interval)?;
+        if let Some(habit_maximum) = self.habit_maximum {
+            write!(f, "/{}", habit_maximum)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Delay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            DelayKind::All => "-",
+            DelayKind::FirstOccurrence => "--",
+        };
+        write!(f, "{}{}", kind, self.interval)
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.to_string_with_weekdays(&ENGLISH_WEEKDAY_ABBREVIATIONS)
+        )
+    }
+}
+
+fn write_timestamp_body(
+    f: &mut impl fmt::Write,
+    timestamp: &Timestamp,
+    weekday_names: &[&str; 7],
+) -> fmt::Result {
+    let date = timestamp.date;
+    write!(
+        f,
+        "{:04}-{:02}-{:02} {}",
+        date.year(),
+        date.month(),
+        date.day(),
+        weekday_names[date.weekday().num_days_from_monday() as usize]
+    )?;
+    if let Some(start) = timestamp.start_time {
+        write!(f, " {:02}:{:02}", start.hour(), start.minute())?;
+        if let Some(end) = timestamp.end_time {
+            write!(f, "-{:02}:{:02}", end.hour(), end.minute())?;
+        }
+    }
+    if let Some(repeater) = timestamp.repeater {
+        write!(f, " {}", repeater)?;
+    }
+    if let Some(delay) = timestamp.delay {
+        write!(f, " {}", delay)?;
+    }
+    Ok(())
+}
+
+impl Timestamp {
+    // Like `to_string()`, but with the day names from `weekday_names`, Monday first
+    pub fn to_string_with_weekdays(&self, weekday_names: &[&str; 7]) -> String {
+        let (open, close) = match self.kind {
+            TimestampKind::Active => ('<', '>'),
+            TimestampKind::Inactive => ('[', ']'),
+        };
+        let mut result = String::new();
+        result.push(open);
+        write_timestamp_body(&mut result, self, weekday_names).unwrap();
+        result.push(close);
+        result
+    }
+}
+
+impl fmt::Display for TimestampRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}--{}", self.start, self.end)
+    }
+}
+
+fn timestamp_at(kind: TimestampKind, date: NaiveDate, start_time: Option<NaiveTime>) -> Timestamp {
+    Timestamp {
+        kind,
+        date,
+        start_time,
+        end_time: None,
+        repeater: None,
+        delay: None,
+    }
+}
+
+// Formats the result of `render()` the way Org inserts timestamps: <2006-06-13 Tue 10:00>.  A date
+// time range within a single day becomes <2006-06-13 Tue 10:00-12:00>, one spanning several days
+// a range of two timestamps.  Week numbers and bare time ranges don't name a day and are
+// rejected.  `weekday_names` go Monday first, e.g. `ENGLISH_WEEKDAY_ABBREVIATIONS`.
+pub fn format_timestamp(
+    rendered: RenderedSpec,
+    kind: TimestampKind,
+    weekday_names: &[&str; 7],
+) -> Result<String> {
+    match rendered {
+        RenderedSpec::Date(date) => {
+            Ok(timestamp_at(kind, date, None).to_string_with_weekdays(weekday_names))
+        }
+        RenderedSpec::DateTime(datetime) => {
+            Ok(timestamp_at(kind, datetime.date(), Some(datetime.time()))
+                .to_string_with_weekdays(weekday_names))
+        }
+        RenderedSpec::DateTimeRange(start, end) if start.date() == end.date() => {
+            let timestamp = Timestamp {
+                end_time: Some(end.time()),
+                ..timestamp_at(kind, start.date(), Some(start.time()))
+            };
+            Ok(timestamp.to_string_with_weekdays(weekday_names))
+        }
+        RenderedSpec::DateTimeRange(start, end) => Ok(format!(
+            "{}--{}",
+            timestamp_at(kind, start.date(), Some(start.time()))
+                .to_string_with_weekdays(weekday_names),
+            timestamp_at(kind, end.date(), Some(end.time())).to_string_with_weekdays(weekday_names)
+        )),
+        RenderedSpec::Week(_) | RenderedSpec::TimeRange(_, _) => {
+            Err(OrgModeDateTimeError::UnrepresentableTimestamp(rendered))
+        }
+    }
+}
+
+// `times` repeats of `interval`, or None if that's too many to count
+fn interval_as_relative(interval: Interval, times: u32) -> Option<Relative> {
+    let count = Some(interval.count.checked_mul(times)?);
+    let relative = match interval.unit {
+        TimeUnit::Hour => Relative {
+            hours: count,
+            ..Default::default()
+        },
+        TimeUnit::Day => Relative {
+            days: count,
+            ..Default::default()
+        },
+        TimeUnit::Week => Relative {
+            weeks: count,
+            ..Default::default()
+        },
+        TimeUnit::Month => Relative {
+            months: count,
+            ..Default::default()
+        },
+        TimeUnit::Year => Relative {
+            years: count,
+            ..Default::default()
+        },
+    };
+    Some(relative)
+}
+
+// The earliest occurrence of a repeated `anchor` not on a day before `today`, and how many
+// repeats it took.  Each occurrence is counted from the anchor rather than the one before, so a
+// monthly repeat of the 31st comes back to the 31st after a shorter month.
+fn next_occurrence(
+    anchor: NaiveDateTime,
+    interval: Interval,
+    today: NaiveDate,
+) -> Result<(NaiveDateTime, u32)> {
+    if interval.count == 0 || anchor.date() >= today {
+        return Ok((anchor, 0));
+    }
+    let count = i64::from(interval.count);
+    let elapsed = today.and_time(NaiveTime::MIN) - anchor;
+    let months = i64::from(today.year() - anchor.year()) * 12 + i64::from(today.month())
+        - i64::from(anchor.month());
+    // An estimate that doesn't overshoot, so only the last few repeats need stepping through
+    let estimate = match interval.unit {
+        TimeUnit::Hour => elapsed.num_hours() / count,
+        TimeUnit::Day => elapsed.num_days() / count,
+        TimeUnit::Week => elapsed.num_days() / (7 * count),
+        TimeUnit::Month => months / count,
+        TimeUnit::Year => months / (12 * count),
+    };
+    let mut times = u32::try_from(estimate.max(0))?;
+    loop {
+        let relative =
+            interval_as_relative(interval, times).ok_or(OrgModeDateTimeError::DateOutOfRange)?;
+        let occurrence = shift_by_relative(anchor, &relative, 1, MonthEnd::Clamp).ok_or(
+            OrgModeDateTimeError::UnrepresentableFutureRelativeDate(relative),
+        )?;
+        if occurrence.date() >= today {
+            return Ok((occurrence, times));
+        }
+        times = times
+            .checked_add(1)
+            .ok_or(OrgModeDateTimeError::DateOutOfRange)?;
+    }
+}
+
+// Whether `today` falls between the start of the deadline's warning period and the deadline
+// itself.  A repeated deadline is first moved to its earliest occurrence not before `today`.  The
+// timestamp's own delay cookie overrides `default_warning` (cf. `org-deadline-warning-days`), but
+// a "--" cookie only does so while the deadline hasn't repeated yet.  Like Org's repeaters, month
+// and year steps that land past the end of a month stop on its last day.
+pub fn is_in_warning_window(
+    deadline: &Timestamp,
+    default_warning: Interval,
+    today: NaiveDate,
+) -> Result<bool> {
+    let anchor = deadline
+        .date
+        .and_time(deadline.start_time.unwrap_or(NaiveTime::MIN));
+    let (occurrence, repeats) = match deadline.repeater {
+        Some(repeater) => next_occurrence(anchor, repeater.interval, today)?,
+        None => (anchor, 0),
+    };
+    let warning = match deadline.delay {
+        Some(Delay {
+            kind: DelayKind::All,
+            interval,
+        }) => interval,
+        Some(Delay {
+            kind: DelayKind::FirstOccurrence,
+            interval,
+        }) if repeats == 0 => interval,
+        _ => default_warning,
+    };
+    let warning = interval_as_relative(warning, 1).ok_or(OrgModeDateTimeError::DateOutOfRange)?;
+    let warning_start = match render_relative_past(warning, occurrence, MonthEnd::Clamp)? {
+        RenderedSpec::Date(date) => date,
+        RenderedSpec::DateTime(datetime) => datetime.date(),
+        rendered => return Err(OrgModeDateTimeError::UnrepresentableTimestamp(rendered)),
+    };
+    Ok(warning_start <= today && today <= occurrence.date())
+}
+
+#[cfg(test)]
+mod test_parse_timestamp {
+    use super::*;
+
+    #[test]
+    fn test_active_date() {
+        assert_eq!(
+            parse_timestamp("<2006-06-13 Tue>").unwrap(),
+            (
+                "",
+                Timestamp {
+                    kind: TimestampKind::Active,
+                    date: NaiveDate::from_ymd_opt(2006, 6, 13).unwrap(),
+                    start_time: None,
+                    end_time: None,
+                    repeater: None,
+                    delay: None,
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_inactive_date_without_weekday() {
+        assert_eq!(
+            parse_timestamp("[2006-06-13]").unwrap(),
+            (
+                "",
+                Timestamp {
+                    kind: TimestampKind::Inactive,
+                    date: NaiveDate::from_ymd_opt(2006, 6, 13).unwrap(),
+                    start_time: None,
+                    end_time: None,
+                    repeater: None,
+                    delay: None,
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_active_date_time() {
+        assert_eq!(
+            parse_timestamp("<2006-06-13 Tue 10:00>").unwrap(),
+            (
+                "",
+                Timestamp {
+                    kind: TimestampKind::Active,
+                    date: NaiveDate::from_ymd_opt(2006, 6, 13).unwrap(),
+                    start_time: NaiveTime::from_hms_opt(10, 0, 0),
+                    end_time: None,
+                    repeater: None,
+                    delay: None,
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_active_date_time_range() {
+        assert_eq!(
+            parse_timestamp("<2006-06-13 Tue 10:00-12:00>").unwrap(),
+            (
+                "",
+                Timestamp {
+                    kind: TimestampKind::Active,
+                    date: NaiveDate::from_ymd_opt(2006, 6, 13).unwrap(),
+                    start_time: NaiveTime::from_hms_opt(10, 0, 0),
+                    end_time: NaiveTime::from_hms_opt(12, 0, 0),
+                    repeater: None,
+                    delay: None,
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_repeaters() {
+        let (input, timestamp) = parse_timestamp("<2006-06-13 Tue +1w>").unwrap();
+        assert_eq!(input, "");
+        assert_eq!(
+            timestamp.repeater,
+            Some(Repeater {
+                kind: RepeaterKind::Cumulate,
+                interval: Interval {
+                    count: 1,
+                    unit: TimeUnit::Week
+                },
+                habit_maximum: None,
+            })
+        );
+
+        let (input, timestamp) = parse_timestamp("<2006-06-13 Tue 10:00 ++1m>").unwrap();
+        assert_eq!(input, "");
+        assert_eq!(timestamp.start_time, NaiveTime::from_hms_opt(10, 0, 0));
+        assert_eq!(
+            timestamp.repeater,
+            Some(Repeater {
+                kind: RepeaterKind::CatchUp,
+                interval: Interval {
+                    count: 1,
+                    unit: TimeUnit::Month
+                },
+                habit_maximum: None,
+            })
+        );
+
+        let (input, timestamp) = parse_timestamp("<2006-06-13 Tue .+2d/4d>").unwrap();
+        assert_eq!(input, "");
+        assert_eq!(
+            timestamp.repeater,
+            Some(Repeater {
+                kind: RepeaterKind::Restart,
+                interval: Interval {
+                    count: 2,
+                    unit: TimeUnit::Day
+                },
+                habit_maximum: Some(Interval {
+                    count: 4,
+                    unit: TimeUnit::Day
+                }),
+            })
+        );
+    }
+
+    #[test]
+    fn test_delays() {
+        let (input, timestamp) = parse_timestamp("<2006-06-13 Tue -3d>").unwrap();
+        assert_eq!(input, "");
+        assert_eq!(
+            timestamp.delay,
+            Some(Delay {
+                kind: DelayKind::All,
+                interval: Interval {
+                    count: 3,
+                    unit: TimeUnit::Day
+                },
+            })
+        );
+
+        let (input, timestamp) = parse_timestamp("<2006-06-13 Tue +1w --2d>").unwrap();
+        assert_eq!(input, "");
+        assert!(timestamp.repeater.is_some());
+        assert_eq!(
+            timestamp.delay,
+            Some(Delay {
+                kind: DelayKind::FirstOccurrence,
+                interval: Interval {
+                    count: 2,
+                    unit: TimeUnit::Day
+                },
+            })
+        );
+
+        let (input, timestamp) = parse_timestamp("<2006-06-13 Tue -2d +1w>").unwrap();
+        assert_eq!(input, "");
+        assert!(timestamp.repeater.is_some());
+        assert!(timestamp.delay.is_some());
+    }
+
+    #[test]
+    fn test_mismatched_brackets() {
+        assert!(parse_timestamp("<2006-06-13 Tue]").is_err());
+        assert!(parse_timestamp("[2006-06-13 Tue>").is_err());
+    }
+
+    #[test]
+    fn test_impossible_date() {
+        assert!(parse_timestamp("<2006-13-45>").is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        for input in [
+            "<2006-06-13 Tue>",
+            "[2006-06-13 Tue]",
+            "<2006-06-13 Tue 10:00>",
+            "[2006-06-13 Tue 09:05-12:00]",
+            "<2006-06-13 Tue +1w>",
+            "<2006-06-13 Tue 10:00 ++1m>",
+            "<2006-06-13 Tue .+2d/4d>",
+            "[2006-06-13 Tue 08:00 +12h]",
+            "<2006-06-13 Tue -3d>",
+            "<2006-06-13 Tue 10:00 +1w --2d>",
+        ] {
+            let (_, timestamp) = parse_timestamp(input).unwrap();
+            assert_eq!(timestamp.to_string(), input);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_warning_window {
+    use super::*;
+
+    const TWO_WEEKS: Interval = Interval {
+        count: 14,
+        unit: TimeUnit::Day,
+    };
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_default_warning() {
+        let (_, deadline) = parse_timestamp("<2006-06-30 Fri>").unwrap();
+        assert!(!is_in_warning_window(&deadline, TWO_WEEKS, date(2006, 6, 15)).unwrap());
+        assert!(is_in_warning_window(&deadline, TWO_WEEKS, date(2006, 6, 16)).unwrap());
+        assert!(is_in_warning_window(&deadline, TWO_WEEKS, date(2006, 6, 30)).unwrap());
+        assert!(!is_in_warning_window(&deadline, TWO_WEEKS, date(2006, 7, 1)).unwrap());
+    }
+
+    #[test]
+    fn test_delay_overrides_default() {
+        let (_, deadline) = parse_timestamp("<2006-06-30 Fri -1m>").unwrap();
+        assert!(is_in_warning_window(&deadline, TWO_WEEKS, date(2006, 5, 30)).unwrap());
+        assert!(!is_in_warning_window(&deadline, TWO_WEEKS, date(2006, 5, 29)).unwrap());
+    }
+
+    #[test]
+    fn test_first_occurrence_delay() {
+        let (_, deadline) = parse_timestamp("<2006-06-30 Fri +1w --2d>").unwrap();
+        assert!(is_in_warning_window(&deadline, TWO_WEEKS, date(2006, 6, 28)).unwrap());
+        assert!(!is_in_warning_window(&deadline, TWO_WEEKS, date(2006, 6, 27)).unwrap());
+        // Next occurrence is Jul 7; "--2d" no longer applies, the default two weeks do
+        assert!(is_in_warning_window(&deadline, TWO_WEEKS, date(2006, 7, 1)).unwrap());
+
+        let (_, deadline) = parse_timestamp("<2006-06-30 Fri +1w -2d>").unwrap();
+        assert!(!is_in_warning_window(&deadline, TWO_WEEKS, date(2006, 7, 1)).unwrap());
+        assert!(is_in_warning_window(&deadline, TWO_WEEKS, date(2006, 7, 5)).unwrap());
+    }
+
+    #[test]
+    fn test_hour_repeater() {
+        let (_, deadline) = parse_timestamp("<2006-06-13 Tue 08:00 +12h -1d>").unwrap();
+        assert!(is_in_warning_window(&deadline, TWO_WEEKS, date(2006, 7, 1)).unwrap());
+        let (_, deadline) = parse_timestamp("<2006-06-13 Tue +1h>").unwrap();
+        assert!(is_in_warning_window(&deadline, TWO_WEEKS, date(2016, 7, 1)).unwrap());
+    }
+
+    #[test]
+    fn test_end_of_month_repeater() {
+        let (_, deadline) = parse_timestamp("<2006-01-31 Tue +1m -1d>").unwrap();
+        // Feb 28 stands in for Feb 31, and March is back on the 31st
+        assert!(is_in_warning_window(&deadline, TWO_WEEKS, date(2006, 2, 27)).unwrap());
+        assert!(is_in_warning_window(&deadline, TWO_WEEKS, date(2006, 2, 28)).unwrap());
+        assert!(!is_in_warning_window(&deadline, TWO_WEEKS, date(2006, 3, 1)).unwrap());
+        assert!(is_in_warning_window(&deadline, TWO_WEEKS, date(2006, 3, 31)).unwrap());
+        // The warning period clamps the same way
+        let (_, deadline) = parse_timestamp("<2006-03-31 Fri -1m>").unwrap();
+        assert!(is_in_warning_window(&deadline, TWO_WEEKS, date(2006, 2, 28)).unwrap());
+        assert!(!is_in_warning_window(&deadline, TWO_WEEKS, date(2006, 2, 27)).unwrap());
+        let (_, deadline) = parse_timestamp("<2004-02-29 Sun +1y>").unwrap();
+        assert!(is_in_warning_window(&deadline, TWO_WEEKS, date(2005, 2, 28)).unwrap());
+    }
+}
+
+#[cfg(test)]
+mod test_timestamp_range {
+    use super::*;
+
+    fn datetime(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let (input, range) = parse_timestamp_range("<2006-06-13 Tue>--<2006-06-15 Thu>").unwrap();
+        assert_eq!(input, "");
+        assert_eq!(
+            range.start.date,
+            NaiveDate::from_ymd_opt(2006, 6, 13).unwrap()
+        );
+        assert_eq!(
+            range.end.date,
+            NaiveDate::from_ymd_opt(2006, 6, 15).unwrap()
+        );
+        assert_eq!(range.to_string(), "<2006-06-13 Tue>--<2006-06-15 Thu>");
+    }
+
+    #[test]
+    fn test_mixed_kinds() {
+        assert!(parse_timestamp_range("<2006-06-13 Tue>--[2006-06-15 Thu]").is_err());
+    }
+
+    #[test]
+    fn test_date_span() {
+        let (_, range) = parse_timestamp_range("<2006-06-13 Tue>--<2006-06-15 Thu>").unwrap();
+        assert_eq!(range.days(), 3);
+        assert_eq!(range.duration(), Some(Duration::days(3)));
+        assert!(range.contains(datetime(2006, 6, 13, 0, 0)));
+        assert!(range.contains(datetime(2006, 6, 15, 23, 59)));
+        assert!(!range.contains(datetime(2006, 6, 16, 0, 0)));
+        assert!(!range.contains(datetime(2006, 6, 12, 23, 59)));
+    }
+
+    #[test]
+    fn test_date_time_span() {
+        let (_, range) =
+            parse_timestamp_range("<2006-06-13 Tue 10:00>--<2006-06-15 Thu 12:00>").unwrap();
+        assert_eq!(range.days(), 3);
+        assert_eq!(
+            range.duration(),
+            Some(Duration::days(2) + Duration::hours(2))
+        );
+        assert!(!range.contains(datetime(2006, 6, 13, 9, 59)));
+        assert!(range.contains(datetime(2006, 6, 13, 10, 0)));
+        assert!(range.contains(datetime(2006, 6, 15, 11, 59)));
+        assert!(!range.contains(datetime(2006, 6, 15, 12, 0)));
+    }
+
+    #[test]
+    fn test_end_of_calendar() {
+        let last = NaiveDate::MAX;
+        let range = TimestampRange {
+            start: timestamp_at(TimestampKind::Active, last, None),
+            end: timestamp_at(TimestampKind::Active, last, None),
+        };
+        let (_, parsed) = parse_timestamp_range(&range.to_string()).unwrap();
+        assert_eq!(parsed, range);
+        assert_eq!(range.days(), 1);
+        assert_eq!(range.end_datetime(), None);
+        assert_eq!(range.duration(), None);
+        assert!(!range.contains(last.and_time(NaiveTime::MIN)));
+
+        let range = TimestampRange {
+            end: timestamp_at(
+                TimestampKind::Active,
+                last,
+                NaiveTime::from_hms_opt(12, 0, 0),
+            ),
+            ..range
+        };
+        assert_eq!(range.duration(), Some(Duration::hours(12)));
+    }
+}
+
+#[cfg(test)]
+mod test_format_timestamp {
+    use super::*;
+
+    const GERMAN_WEEKDAY_ABBREVIATIONS: [&str; 7] = ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"];
+
+    fn datetime(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_date() {
+        let rendered = RenderedSpec::Date(NaiveDate::from_ymd_opt(2006, 6, 13).unwrap());
+        let formatted = format_timestamp(
+            rendered,
+            TimestampKind::Active,
+            &ENGLISH_WEEKDAY_ABBREVIATIONS,
+        )
+        .unwrap();
+        assert_eq!(formatted, "<2006-06-13 Tue>");
+        let (input, timestamp) = parse_timestamp(&formatted).unwrap();
+        assert_eq!(input, "");
+        assert_eq!(
+            timestamp.date,
+            NaiveDate::from_ymd_opt(2006, 6, 13).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_date_time() {
+        let rendered = RenderedSpec::DateTime(datetime(2006, 6, 13, 9, 5));
+        let formatted = format_timestamp(
+            rendered,
+            TimestampKind::Inactive,
+            &ENGLISH_WEEKDAY_ABBREVIATIONS,
+        )
+        .unwrap();
+        assert_eq!(formatted, "[2006-06-13 Tue 09:05]");
+        let (input, timestamp) = parse_timestamp(&formatted).unwrap();
+        assert_eq!(input, "");
+        assert_eq!(timestamp.start_time, NaiveTime::from_hms_opt(9, 5, 0));
+    }
+
+    #[test]
+    fn test_date_time_range() {
+        let rendered =
+            RenderedSpec::DateTimeRange(datetime(2006, 6, 13, 10, 0), datetime(2006, 6, 13, 12, 0));
+        let formatted = format_timestamp(
+            rendered,
+            TimestampKind::Active,
+            &ENGLISH_WEEKDAY_ABBREVIATIONS,
+        )
+        .unwrap();
+        assert_eq!(formatted, "<2006-06-13 Tue 10:00-12:00>");
+        let (input, timestamp) = parse_timestamp(&formatted).unwrap();
+        assert_eq!(input, "");
+        assert_eq!(timestamp.end_time, NaiveTime::from_hms_opt(12, 0, 0));
+
+        let rendered =
+            RenderedSpec::DateTimeRange(datetime(2006, 6, 13, 22, 0), datetime(2006, 6, 14, 1, 0));
+        let formatted = format_timestamp(
+            rendered,
+            TimestampKind::Active,
+            &ENGLISH_WEEKDAY_ABBREVIATIONS,
+        )
+        .unwrap();
+        assert_eq!(formatted, "<2006-06-13 Tue 22:00>--<2006-06-14 Wed 01:00>");
+        let (input, _) = parse_timestamp_range(&formatted).unwrap();
+        assert_eq!(input, "");
+    }
+
+    #[test]
+    fn test_localized_weekdays() {
+        let rendered = RenderedSpec::DateTime(datetime(2006, 6, 13, 10, 0));
+        let formatted = format_timestamp(
+            rendered,
+            TimestampKind::Active,
+            &GERMAN_WEEKDAY_ABBREVIATIONS,
+        )
+        .unwrap();
+        assert_eq!(formatted, "<2006-06-13 Di 10:00>");
+        let (input, timestamp) = parse_timestamp(&formatted).unwrap();
+        assert_eq!(input, "");
+        assert_eq!(
+            timestamp.to_string_with_weekdays(&GERMAN_WEEKDAY_ABBREVIATIONS),
+            formatted
+        );
+    }
+
+    #[test]
+    fn test_no_date() {
+        assert!(matches!(
+            format_timestamp(
+                RenderedSpec::Week(4),
+                TimestampKind::Active,
+                &ENGLISH_WEEKDAY_ABBREVIATIONS
+            ),
+            Err(OrgModeDateTimeError::UnrepresentableTimestamp(
+                RenderedSpec::Week(4)
+            ))
+        ));
+    }
+}
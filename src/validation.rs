@@ -0,0 +1,381 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::error::{OrgModeDateTimeError, Result};
+use crate::parser::{
+    Absolute, AbsoluteTime, DateTimeSpec, Meridiem, Relative, RelativeTime, TimeTrailer, Zone,
+};
+use crate::span::Field;
+
+// Range checks on what the parser accepted, so that "2006-13-45", "w99" or "25:99" turn into
+// errors naming the field rather than panics in the renderer
+
+fn check(field: Field, value: u32, valid: bool) -> Result<()> {
+    if valid {
+        Ok(())
+    } else {
+        Err(OrgModeDateTimeError::FieldOutOfRange { field, value })
+    }
+}
+
+pub(crate) fn describe_fields(fields: &[Field]) -> String {
+    let names: Vec<String> = fields.iter().map(Field::to_string).collect();
+    names.join(", ")
+}
+
+fn populated<const N: usize>(fields: [(bool, Field); N]) -> Vec<Field> {
+    fields
+        .into_iter()
+        .filter(|(is_some, _)| *is_some)
+        .map(|(_, field)| field)
+        .collect()
+}
+
+// The year a date will be rendered in, unless it's abbreviated and so depends on the
+// current date
+fn full_year(year: Option<u32>) -> Option<i32> {
+    year.filter(|year| *year >= 10)
+        .and_then(|year| i32::try_from(year).ok())
+}
+
+fn last_day_of_month(year: Option<i32>, month: u32) -> u32 {
+    match year {
+        Some(year) => (28..=31)
+            .rev()
+            .find(|day| NaiveDate::from_ymd_opt(year, month, *day).is_some())
+            .unwrap_or(31),
+        None => match month {
+            2 => 29,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        },
+    }
+}
+
+fn last_iso_week(year: Option<i32>) -> u32 {
+    match year.and_then(|year| NaiveDate::from_isoywd_opt(year, 53, Weekday::Mon)) {
+        Some(_) => 53,
+        None if year.is_some() => 52,
+        None => 53,
+    }
+}
+
+fn validate_time(
+    hour: Option<u32>,
+    minute: Option<u32>,
+    meridiem: Option<Meridiem>,
+    fields: [Field; 2],
+) -> Result<()> {
+    let [hour_field, minute_field] = fields;
+    if let Some(hour) = hour {
+        let hours = if meridiem.is_some() { 1..=12 } else { 0..=23 };
+        check(hour_field, hour, hours.contains(&hour))?;
+    }
+    if let Some(minute) = minute {
+        check(minute_field, minute, minute < 60)?;
+    }
+    Ok(())
+}
+
+// Offsets of a day or more aren't offsets
+fn validate_zone(zone: Option<Zone>, field: Field) -> Result<()> {
+    if let Some(Zone::Offset(seconds)) = zone {
+        let seconds = seconds.unsigned_abs();
+        check(field, seconds, seconds < 24 * 60 * 60)?;
+    }
+    Ok(())
+}
+
+fn validate_time_range_end(end: &AbsoluteTime) -> Result<()> {
+    validate_time(
+        end.hour,
+        end.minute,
+        end.meridiem,
+        [Field::EndHour, Field::EndMinute],
+    )?;
+    validate_zone(end.zone, Field::EndZone)
+}
+
+impl Absolute {
+    // What the renderer reports when it can't make sense of the combination
+    pub(crate) fn populated_fields(&self) -> Vec<Field> {
+        populated([
+            (self.year.is_some(), Field::Year),
+            (self.month.is_some(), Field::Month),
+            (self.day.is_some(), Field::Day),
+            (self.week.is_some(), Field::Week),
+            (self.weekday.is_some(), Field::Weekday),
+            (self.hour.is_some(), Field::Hour),
+            (self.minute.is_some(), Field::Minute),
+            (self.meridiem.is_some(), Field::Meridiem),
+            (self.zone.is_some(), Field::Zone),
+        ])
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let max_year = NaiveDate::MAX.year().unsigned_abs();
+        if let Some(year) = self.year {
+            check(Field::Year, year, year <= max_year)?;
+        }
+        let year = full_year(self.year);
+        if let Some(month) = self.month {
+            check(Field::Month, month, (1..=12).contains(&month))?;
+        }
+        if let Some(day) = self.day {
+            let last_day = self
+                .month
+                .map_or(31, |month| last_day_of_month(year, month));
+            check(Field::Day, day, (1..=last_day).contains(&day))?;
+        }
+        if let Some(week) = self.week {
+            check(Field::Week, week, (1..=last_iso_week(year)).contains(&week))?;
+        }
+        if let Some(weekday) = self.weekday {
+            check(Field::Weekday, weekday, (1..=7).contains(&weekday))?;
+        }
+        validate_time(
+            self.hour,
+            self.minute,
+            self.meridiem,
+            [Field::Hour, Field::Minute],
+        )?;
+        validate_zone(self.zone, Field::Zone)
+    }
+}
+
+impl AbsoluteTime {
+    pub(crate) fn populated_fields(&self) -> Vec<Field> {
+        populated([
+            (self.hour.is_some(), Field::Hour),
+            (self.minute.is_some(), Field::Minute),
+            (self.meridiem.is_some(), Field::Meridiem),
+            (self.zone.is_some(), Field::Zone),
+        ])
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        validate_time(
+            self.hour,
+            self.minute,
+            self.meridiem,
+            [Field::Hour, Field::Minute],
+        )?;
+        validate_zone(self.zone, Field::Zone)
+    }
+}
+
+impl Relative {
+    pub(crate) fn populated_fields(&self) -> Vec<Field> {
+        populated([
+            (self.hours.is_some(), Field::Hours),
+            (self.days.is_some(), Field::Days),
+            (self.weeks.is_some(), Field::Weeks),
+            (self.weekdays.is_some(), Field::Weekdays),
+            (self.months.is_some(), Field::Months),
+            (self.years.is_some(), Field::Years),
+        ])
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if let Some(weekdays) = self.weekdays {
+            check(Field::Weekdays, weekdays, (1..=7).contains(&weekdays))?;
+        }
+        Ok(())
+    }
+}
+
+impl RelativeTime {
+    pub(crate) fn populated_fields(&self) -> Vec<Field> {
+        populated([
+            (self.hours.is_some(), Field::DurationHours),
+            (self.minutes.is_some(), Field::DurationMinutes),
+        ])
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if let Some(minutes) = self.minutes {
+            check(Field::DurationMinutes, minutes, minutes < 60)?;
+        }
+        Ok(())
+    }
+}
+
+impl TimeTrailer {
+    pub(crate) fn populated_fields(&self) -> Vec<Field> {
+        match self {
+            TimeTrailer::Time(time) => time.populated_fields(),
+            TimeTrailer::RangeAbsoluteEnd(start, end) => {
+                let end = end.populated_fields().into_iter().map(Field::as_range_end);
+                start.populated_fields().into_iter().chain(end).collect()
+            }
+            TimeTrailer::RangeRelativeEnd(start, duration) => {
+                let mut fields = start.populated_fields();
+                fields.extend(duration.populated_fields());
+                fields
+            }
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            TimeTrailer::Time(time) => time.validate(),
+            TimeTrailer::RangeAbsoluteEnd(start, end) => {
+                start.validate()?;
+                validate_time_range_end(end)
+            }
+            TimeTrailer::RangeRelativeEnd(start, duration) => {
+                start.validate()?;
+                duration.validate()
+            }
+        }
+    }
+}
+
+impl DateTimeSpec {
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            DateTimeSpec::Absolute(absolute) => absolute.validate(),
+            DateTimeSpec::NowRelativeFuture(relative)
+            | DateTimeSpec::NowRelativePast(relative)
+            | DateTimeSpec::DefaultRelativeFuture(relative)
+            | DateTimeSpec::DefaultRelativePast(relative) => relative.validate(),
+            DateTimeSpec::NowRelativeFutureWithTime(relative, trailer)
+            | DateTimeSpec::NowRelativePastWithTime(relative, trailer) => {
+                relative.validate()?;
+                trailer.validate()
+            }
+            DateTimeSpec::TimeRangeAbsoluteStartAbsoluteEnd(start, end) => {
+                start.validate()?;
+                validate_time_range_end(end)
+            }
+            DateTimeSpec::TimeRangeAbsoluteStartRelativeEnd(start, duration) => {
+                start.validate()?;
+                duration.validate()
+            }
+            DateTimeSpec::DateTimeRangeAbsoluteStartAbsoluteEnd(start, end) => {
+                start.validate()?;
+                validate_time_range_end(end)
+            }
+            DateTimeSpec::DateTimeRangeAbsoluteStartRelativeEnd(start, duration) => {
+                start.validate()?;
+                duration.validate()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_validate {
+    use super::*;
+    use crate::parser::parse_strict;
+
+    fn out_of_range(input: &str) -> (Field, u32) {
+        match parse_strict(input).unwrap().validate() {
+            Err(OrgModeDateTimeError::FieldOutOfRange { field, value }) => (field, value),
+            other => panic!("expected {:?} to be out of range, got {:?}", input, other),
+        }
+    }
+
+    #[test]
+    fn test_valid() {
+        for input in [
+            "2006-06-13 14:00",
+            "2004-2-29",
+            "2/29",
+            "sep 30",
+            "2015-w53-4",
+            "w53",
+            "fri 12pm",
+            "23:59",
+            "11am-1pm",
+            "9:00+1:30",
+            "+2sun",
+            "+100d",
+        ] {
+            assert!(
+                parse_strict(input).unwrap().validate().is_ok(),
+                "{:?} should be valid",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_date() {
+        assert_eq!(out_of_range("2006-13-45"), (Field::Month, 13));
+        assert_eq!(out_of_range("2006-0-1"), (Field::Month, 0));
+        assert_eq!(out_of_range("2006-4-31"), (Field::Day, 31));
+        assert_eq!(out_of_range("2006-2-29"), (Field::Day, 29));
+        assert_eq!(out_of_range("2/30"), (Field::Day, 30));
+        assert_eq!(out_of_range("32"), (Field::Day, 32));
+        assert_eq!(out_of_range("0"), (Field::Day, 0));
+        assert_eq!(out_of_range("300000-1-1"), (Field::Year, 300000));
+    }
+
+    #[test]
+    fn test_week() {
+        assert_eq!(out_of_range("w99"), (Field::Week, 99));
+        assert_eq!(out_of_range("w0"), (Field::Week, 0));
+        assert_eq!(out_of_range("2014-w53-1"), (Field::Week, 53));
+        assert_eq!(out_of_range("2012-w4-8"), (Field::Weekday, 8));
+    }
+
+    #[test]
+    fn test_time() {
+        assert_eq!(out_of_range("25:99"), (Field::Hour, 25));
+        assert_eq!(out_of_range("24:00"), (Field::Hour, 24));
+        assert_eq!(out_of_range("12:60"), (Field::Minute, 60));
+        assert_eq!(out_of_range("13pm"), (Field::Hour, 13));
+        assert_eq!(out_of_range("0pm"), (Field::Hour, 0));
+        assert_eq!(out_of_range("0am"), (Field::Hour, 0));
+        assert_eq!(out_of_range("11am-0pm"), (Field::EndHour, 0));
+        assert_eq!(out_of_range("2006-06-13 14:75"), (Field::Minute, 75));
+        assert_eq!(out_of_range("11am-13pm"), (Field::EndHour, 13));
+        assert_eq!(out_of_range("fri 9:00-9:60"), (Field::EndMinute, 60));
+        assert_eq!(out_of_range("9:00+1:60"), (Field::DurationMinutes, 60));
+        assert_eq!(out_of_range("tomorrow 25:00"), (Field::Hour, 25));
+    }
+
+    #[test]
+    fn test_overflow() {
+        // Too large to parse at all, so it's an error before there's anything to validate
+        for input in ["99999999999-1-1", "w99999999999"] {
+            assert!(
+                matches!(
+                    parse_strict(input),
+                    Err(OrgModeDateTimeError::UnexpectedInput { .. })
+                ),
+                "{:?} should be rejected",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_zone() {
+        assert!(parse_strict("9am +23:59").unwrap().validate().is_ok());
+        let spec = DateTimeSpec::Absolute(Absolute {
+            hour: Some(9),
+            zone: Some(Zone::Offset(-86400)),
+            ..Default::default()
+        });
+        assert!(matches!(
+            spec.validate(),
+            Err(OrgModeDateTimeError::FieldOutOfRange {
+                field: Field::Zone,
+                value: 86400
+            })
+        ));
+    }
+
+    #[test]
+    fn test_message() {
+        assert_eq!(
+            parse_strict("2006-13-1")
+                .unwrap()
+                .validate()
+                .unwrap_err()
+                .to_string(),
+            "month 13 is out of range"
+        );
+    }
+}
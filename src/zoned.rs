@@ -0,0 +1,483 @@
+use chrono::{
+    DateTime, Duration, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeZone,
+};
+
+use crate::error::{OrgModeDateTimeError, Result};
+use crate::parser::{Absolute, AbsoluteTime, DateTimeSpec, Relative, TimeTrailer, Zone};
+use crate::renderer::{
+    shift_by_relative, AmbiguousTime, DatePreference, MonthEnd, NonexistentTime, RenderOptions,
+    RenderedSpec, Renderer,
+};
+use crate::span::Field;
+
+// RenderedSpec with its date-times placed in a time zone
+#[derive(Debug, PartialEq, Eq)]
+pub enum ZonedRenderedSpec<Tz: TimeZone> {
+    Date(NaiveDate),
+    DateTime(DateTime<Tz>),
+    Week(u32),
+    TimeRange(NaiveTime, NaiveTime),
+    DateTimeRange(DateTime<Tz>, DateTime<Tz>),
+}
+
+// Like Renderer, but for `now` and `default` in a time zone.  Dates and wall-clock times are
+// worked out as usual and then placed in the zone, per the nonexistent and ambiguous time
+// policies of the options.  Offsets in hours ("+2h", "--1d3h") are the exception: they count
+// elapsed time, so "+2h" across a DST change is still two hours away.  A time written with a
+// zone ("14:00 UTC") is worked out in that zone and converted into the renderer's.
+#[derive(Debug, Clone)]
+pub struct ZonedRenderer<Tz: TimeZone> {
+    now: DateTime<Tz>,
+    default: DateTime<Tz>,
+    options: RenderOptions,
+}
+
+impl<Tz: TimeZone> ZonedRenderer<Tz> {
+    pub fn new(now: DateTime<Tz>) -> ZonedRenderer<Tz> {
+        ZonedRenderer {
+            default: now.clone(),
+            now,
+            options: RenderOptions::default(),
+        }
+    }
+
+    pub fn with_default(mut self, default: DateTime<Tz>) -> ZonedRenderer<Tz> {
+        self.default = default;
+        self
+    }
+
+    pub fn with_options(mut self, options: RenderOptions) -> ZonedRenderer<Tz> {
+        self.options = options;
+        self
+    }
+
+    pub fn prefer(mut self, prefer: DatePreference) -> ZonedRenderer<Tz> {
+        self.options.prefer = prefer;
+        self
+    }
+
+    pub fn on_nonexistent(mut self, policy: NonexistentTime) -> ZonedRenderer<Tz> {
+        self.options.nonexistent = policy;
+        self
+    }
+
+    pub fn on_ambiguous(mut self, policy: AmbiguousTime) -> ZonedRenderer<Tz> {
+        self.options.ambiguous = policy;
+        self
+    }
+
+    pub fn now(&self) -> &DateTime<Tz> {
+        &self.now
+    }
+
+    pub fn default_date(&self) -> &DateTime<Tz> {
+        &self.default
+    }
+
+    pub fn options(&self) -> &RenderOptions {
+        &self.options
+    }
+
+    pub fn render(&self, spec: DateTimeSpec) -> Result<ZonedRenderedSpec<Tz>> {
+        match written_zone(&spec)? {
+            None => self.render_here(spec),
+            Some(Zone::Offset(seconds)) => {
+                let zone = FixedOffset::east_opt(seconds).ok_or(
+                    OrgModeDateTimeError::FieldOutOfRange {
+                        field: Field::Zone,
+                        value: seconds.unsigned_abs(),
+                    },
+                )?;
+                self.render_from(&zone, without_zone(spec))
+            }
+            Some(Zone::Named(zone)) => self.render_from(&zone, without_zone(spec)),
+        }
+    }
+
+    // Renders `spec` as if written in `zone`, with the result converted into this renderer's zone
+    fn render_from<Z: TimeZone>(
+        &self,
+        zone: &Z,
+        spec: DateTimeSpec,
+    ) -> Result<ZonedRenderedSpec<Tz>> {
+        let there = ZonedRenderer {
+            now: self.now.with_timezone(zone),
+            default: self.default.with_timezone(zone),
+            options: self.options.clone(),
+        };
+        let here = self.now.timezone();
+        let converted = match there.render_here(spec)? {
+            ZonedRenderedSpec::Date(date) => ZonedRenderedSpec::Date(date),
+            ZonedRenderedSpec::DateTime(datetime) => {
+                ZonedRenderedSpec::DateTime(datetime.with_timezone(&here))
+            }
+            ZonedRenderedSpec::Week(week) => ZonedRenderedSpec::Week(week),
+            // Times without a date are taken to be on today's date there
+            ZonedRenderedSpec::TimeRange(start, end) => {
+                let today = there.now.date_naive();
+                let convert = |time: NaiveTime| -> Result<NaiveTime> {
+                    let datetime = there.resolve(today.and_time(time))?;
+                    Ok(datetime.with_timezone(&here).time())
+                };
+                ZonedRenderedSpec::TimeRange(convert(start)?, convert(end)?)
+            }
+            ZonedRenderedSpec::DateTimeRange(start, end) => ZonedRenderedSpec::DateTimeRange(
+                start.with_timezone(&here),
+                end.with_timezone(&here),
+            ),
+        };
+        Ok(converted)
+    }
+
+    fn render_here(&self, spec: DateTimeSpec) -> Result<ZonedRenderedSpec<Tz>> {
+        match spec {
+            DateTimeSpec::NowRelativeFuture(relative) if is_elapsed_time(&relative) => {
+                self.shift(&self.now, relative, 1)
+            }
+            DateTimeSpec::NowRelativePast(relative) if is_elapsed_time(&relative) => {
+                self.shift(&self.now, relative, -1)
+            }
+            DateTimeSpec::DefaultRelativeFuture(relative) if is_elapsed_time(&relative) => {
+                self.shift(&self.default, relative, 1)
+            }
+            DateTimeSpec::DefaultRelativePast(relative) if is_elapsed_time(&relative) => {
+                self.shift(&self.default, relative, -1)
+            }
+            spec => {
+                let rendered = Renderer::new(self.now.naive_local())
+                    .with_default(self.default.naive_local())
+                    .with_options(self.options.clone())
+                    .render(spec)?;
+                self.localize(rendered)
+            }
+        }
+    }
+
+    // Moves by whole days and longer on the calendar, keeping the wall-clock time, and then by
+    // the hours on the clock
+    fn shift(
+        &self,
+        baseline: &DateTime<Tz>,
+        relative: Relative,
+        sign: i64,
+    ) -> Result<ZonedRenderedSpec<Tz>> {
+        relative.validate()?;
+        let unrepresentable = || {
+            if sign > 0 {
+                OrgModeDateTimeError::UnrepresentableFutureRelativeDate(relative.clone())
+            } else {
+                OrgModeDateTimeError::UnrepresentablePastRelativeDate(relative.clone())
+            }
+        };
+        let calendar = Relative {
+            hours: None,
+            ..relative.clone()
+        };
+        let start = if calendar == Relative::default() {
+            baseline.clone()
+        } else {
+            let local = shift_by_relative(baseline.naive_local(), &calendar, sign, MonthEnd::Error)
+                .ok_or_else(unrepresentable)?;
+            self.resolve(local)?
+        };
+        let hours = Duration::hours(sign * i64::from(relative.hours.unwrap_or(0)));
+        let shifted = start
+            .checked_add_signed(hours)
+            .ok_or_else(unrepresentable)?;
+        Ok(ZonedRenderedSpec::DateTime(shifted))
+    }
+
+    fn localize(&self, rendered: RenderedSpec) -> Result<ZonedRenderedSpec<Tz>> {
+        let localized = match rendered {
+            RenderedSpec::Date(date) => ZonedRenderedSpec::Date(date),
+            RenderedSpec::DateTime(datetime) => {
+                ZonedRenderedSpec::DateTime(self.resolve(datetime)?)
+            }
+            RenderedSpec::Week(week) => ZonedRenderedSpec::Week(week),
+            RenderedSpec::TimeRange(start, end) => ZonedRenderedSpec::TimeRange(start, end),
+            RenderedSpec::DateTimeRange(start, end) => {
+                ZonedRenderedSpec::DateTimeRange(self.resolve(start)?, self.resolve(end)?)
+            }
+        };
+        Ok(localized)
+    }
+
+    fn resolve(&self, local: NaiveDateTime) -> Result<DateTime<Tz>> {
+        let zone = self.now.timezone();
+        match zone.from_local_datetime(&local) {
+            LocalResult::Single(datetime) => Ok(datetime),
+            LocalResult::Ambiguous(earliest, latest) => match self.options.ambiguous {
+                AmbiguousTime::Earliest => Ok(earliest),
+                AmbiguousTime::Latest => Ok(latest),
+                AmbiguousTime::Error => Err(OrgModeDateTimeError::AmbiguousLocalTime(local)),
+            },
+            LocalResult::None => match self.options.nonexistent {
+                NonexistentTime::ShiftForward => shift_forward(&zone, local),
+                NonexistentTime::Error => Err(OrgModeDateTimeError::NonexistentLocalTime(local)),
+            },
+        }
+    }
+}
+
+// The zone the times of `spec` are written in.  It's normally on the start of a range, but may
+// be on either end as long as they agree.
+fn written_zone(spec: &DateTimeSpec) -> Result<Option<Zone>> {
+    let range_zone = |start: Option<Zone>, end: Option<Zone>| match (start, end) {
+        (Some(start), Some(end)) if start != end => {
+            Err(OrgModeDateTimeError::UnsupportedFields(vec![
+                Field::Zone,
+                Field::EndZone,
+            ]))
+        }
+        (start, end) => Ok(start.or(end)),
+    };
+    let trailer_zone = |trailer: &TimeTrailer| match trailer {
+        TimeTrailer::Time(time) => Ok(time.zone),
+        TimeTrailer::RangeAbsoluteEnd(start, end) => range_zone(start.zone, end.zone),
+        TimeTrailer::RangeRelativeEnd(start, _) => Ok(start.zone),
+    };
+    match spec {
+        DateTimeSpec::Absolute(absolute) => Ok(absolute.zone),
+        DateTimeSpec::NowRelativeFuture(_)
+        | DateTimeSpec::NowRelativePast(_)
+        | DateTimeSpec::DefaultRelativeFuture(_)
+        | DateTimeSpec::DefaultRelativePast(_) => Ok(None),
+        DateTimeSpec::NowRelativeFutureWithTime(_, trailer)
+        | DateTimeSpec::NowRelativePastWithTime(_, trailer) => trailer_zone(trailer),
+        DateTimeSpec::TimeRangeAbsoluteStartAbsoluteEnd(start, end) => {
+            range_zone(start.zone, end.zone)
+        }
+        DateTimeSpec::TimeRangeAbsoluteStartRelativeEnd(start, _) => Ok(start.zone),
+        DateTimeSpec::DateTimeRangeAbsoluteStartAbsoluteEnd(start, end) => {
+            range_zone(start.zone, end.zone)
+        }
+        DateTimeSpec::DateTimeRangeAbsoluteStartRelativeEnd(start, _) => Ok(start.zone),
+    }
+}
+
+fn without_zone(spec: DateTimeSpec) -> DateTimeSpec {
+    let absolute = |absolute: Absolute| Absolute {
+        zone: None,
+        ..absolute
+    };
+    let time = |time: AbsoluteTime| AbsoluteTime { zone: None, ..time };
+    let trailer = |trailer: TimeTrailer| match trailer {
+        TimeTrailer::Time(start) => TimeTrailer::Time(time(start)),
+        TimeTrailer::RangeAbsoluteEnd(start, end) => {
+            TimeTrailer::RangeAbsoluteEnd(time(start), time(end))
+        }
+        TimeTrailer::RangeRelativeEnd(start, end) => {
+            TimeTrailer::RangeRelativeEnd(time(start), end)
+        }
+    };
+    match spec {
+        DateTimeSpec::Absolute(start) => DateTimeSpec::Absolute(absolute(start)),
+        DateTimeSpec::NowRelativeFutureWithTime(relative, time) => {
+            DateTimeSpec::NowRelativeFutureWithTime(relative, trailer(time))
+        }
+        DateTimeSpec::NowRelativePastWithTime(relative, time) => {
+            DateTimeSpec::NowRelativePastWithTime(relative, trailer(time))
+        }
+        DateTimeSpec::TimeRangeAbsoluteStartAbsoluteEnd(start, end) => {
+            DateTimeSpec::TimeRangeAbsoluteStartAbsoluteEnd(time(start), time(end))
+        }
+        DateTimeSpec::TimeRangeAbsoluteStartRelativeEnd(start, end) => {
+            DateTimeSpec::TimeRangeAbsoluteStartRelativeEnd(time(start), end)
+        }
+        DateTimeSpec::DateTimeRangeAbsoluteStartAbsoluteEnd(start, end) => {
+            DateTimeSpec::DateTimeRangeAbsoluteStartAbsoluteEnd(absolute(start), time(end))
+        }
+        DateTimeSpec::DateTimeRangeAbsoluteStartRelativeEnd(start, end) => {
+            DateTimeSpec::DateTimeRangeAbsoluteStartRelativeEnd(absolute(start), end)
+        }
+        relative => relative,
+    }
+}
+
+// Hours, possibly on top of days and longer, but not the likes of "+2fri"
+fn is_elapsed_time(relative: &Relative) -> bool {
+    relative.hours.is_some() && relative.weekdays.is_none()
+}
+
+// Reads `local` with the offset in force before the gap it falls into, which lands it as far
+// past the end of the gap as it was past the start
+fn shift_forward<Tz: TimeZone>(zone: &Tz, local: NaiveDateTime) -> Result<DateTime<Tz>> {
+    let nonexistent = || OrgModeDateTimeError::NonexistentLocalTime(local);
+    // Zones have skipped a whole day, but never more
+    let before = (1..=24 * 60)
+        .find_map(|minutes| {
+            let earlier = local.checked_sub_signed(Duration::minutes(minutes))?;
+            zone.offset_from_local_datetime(&earlier).latest()
+        })
+        .ok_or_else(nonexistent)?;
+    let offset = Duration::seconds(before.fix().local_minus_utc().into());
+    let utc = local.checked_sub_signed(offset).ok_or_else(nonexistent)?;
+    Ok(zone.from_utc_datetime(&utc))
+}
+
+#[cfg(test)]
+mod test_zoned_renderer {
+    use chrono_tz::Europe::Warsaw;
+    use chrono_tz::Tz;
+
+    use super::*;
+    use crate::parser::parse_strict;
+
+    fn warsaw(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Tz> {
+        Warsaw
+            .with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .earliest()
+            .unwrap()
+    }
+
+    fn rendered(renderer: &ZonedRenderer<Tz>, input: &str) -> Result<ZonedRenderedSpec<Tz>> {
+        renderer.render(parse_strict(input).unwrap())
+    }
+
+    fn offset_hours(rendered: &ZonedRenderedSpec<Tz>) -> i32 {
+        match rendered {
+            ZonedRenderedSpec::DateTime(datetime) => {
+                datetime.offset().fix().local_minus_utc() / 3600
+            }
+            other => panic!("expected a date-time, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_hours_count_elapsed_time() {
+        // Clocks go from 2:00 to 3:00 on 2024-03-31
+        let renderer = ZonedRenderer::new(warsaw(2024, 3, 31, 1, 30));
+        assert_eq!(
+            rendered(&renderer, "+2h").unwrap(),
+            ZonedRenderedSpec::DateTime(warsaw(2024, 3, 31, 4, 30))
+        );
+        let renderer = ZonedRenderer::new(warsaw(2024, 3, 30, 1, 30));
+        assert_eq!(
+            rendered(&renderer, "+1d2h").unwrap(),
+            ZonedRenderedSpec::DateTime(warsaw(2024, 3, 31, 4, 30))
+        );
+        assert_eq!(
+            rendered(&renderer, "+1d").unwrap(),
+            ZonedRenderedSpec::Date(NaiveDate::from_ymd_opt(2024, 3, 31).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_default_date() {
+        let renderer =
+            ZonedRenderer::new(warsaw(2024, 6, 1, 12, 0)).with_default(warsaw(2024, 10, 27, 1, 0));
+        assert_eq!(
+            rendered(&renderer, "++3h").unwrap(),
+            ZonedRenderedSpec::DateTime(warsaw(2024, 10, 27, 3, 0))
+        );
+        assert_eq!(
+            rendered(&renderer, "--1h").unwrap(),
+            ZonedRenderedSpec::DateTime(warsaw(2024, 10, 27, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_nonexistent_time() {
+        let renderer = ZonedRenderer::new(warsaw(2024, 3, 30, 12, 0));
+        assert!(matches!(
+            rendered(&renderer, "2024-03-31 2:30"),
+            Err(OrgModeDateTimeError::NonexistentLocalTime(local))
+                if local.to_string() == "2024-03-31 02:30:00"
+        ));
+        let renderer = renderer.on_nonexistent(NonexistentTime::ShiftForward);
+        assert_eq!(
+            rendered(&renderer, "2024-03-31 2:30").unwrap(),
+            ZonedRenderedSpec::DateTime(warsaw(2024, 3, 31, 3, 30))
+        );
+    }
+
+    #[test]
+    fn test_ambiguous_time() {
+        // Clocks go from 3:00 back to 2:00 on 2024-10-27
+        let renderer = ZonedRenderer::new(warsaw(2024, 10, 26, 12, 0));
+        assert!(matches!(
+            rendered(&renderer, "2024-10-27 2:30"),
+            Err(OrgModeDateTimeError::AmbiguousLocalTime(_))
+        ));
+        let earliest = rendered(
+            &renderer.clone().on_ambiguous(AmbiguousTime::Earliest),
+            "2024-10-27 2:30",
+        )
+        .unwrap();
+        assert_eq!(offset_hours(&earliest), 2);
+        let latest = rendered(
+            &renderer.on_ambiguous(AmbiguousTime::Latest),
+            "2024-10-27 2:30",
+        )
+        .unwrap();
+        assert_eq!(offset_hours(&latest), 1);
+    }
+
+    #[test]
+    fn test_written_zone() {
+        let renderer = ZonedRenderer::new(warsaw(2024, 6, 13, 8, 0));
+        assert_eq!(
+            rendered(&renderer, "2024-06-13 14:00 UTC").unwrap(),
+            ZonedRenderedSpec::DateTime(warsaw(2024, 6, 13, 16, 0))
+        );
+        assert_eq!(
+            rendered(&renderer, "2024-01-10 9am +02:00").unwrap(),
+            ZonedRenderedSpec::DateTime(warsaw(2024, 1, 10, 8, 0))
+        );
+        // 1:00 in New York is already tomorrow here, but not yet today's 1:00 there
+        assert_eq!(
+            rendered(&renderer, "1:00 America/New_York").unwrap(),
+            ZonedRenderedSpec::DateTime(warsaw(2024, 6, 14, 7, 0))
+        );
+        assert_eq!(
+            rendered(&renderer, "9:00-10:30 UTC").unwrap(),
+            ZonedRenderedSpec::TimeRange(
+                NaiveTime::from_hms_opt(11, 0, 0).unwrap(),
+                NaiveTime::from_hms_opt(12, 30, 0).unwrap()
+            )
+        );
+        assert_eq!(
+            rendered(&renderer, "fri 9:00+1:30 Z").unwrap(),
+            ZonedRenderedSpec::DateTimeRange(
+                warsaw(2024, 6, 14, 11, 0),
+                warsaw(2024, 6, 14, 12, 30)
+            )
+        );
+    }
+
+    #[test]
+    fn test_written_zone_with_dst() {
+        // Warsaw's 2:30 doesn't exist on 2024-03-31, but it's only 0:30 UTC
+        let renderer = ZonedRenderer::new(warsaw(2024, 3, 30, 12, 0));
+        assert_eq!(
+            rendered(&renderer, "2024-03-31 0:30 UTC").unwrap(),
+            ZonedRenderedSpec::DateTime(warsaw(2024, 3, 31, 1, 30))
+        );
+        let utc = ZonedRenderer::new(
+            chrono_tz::UTC
+                .with_ymd_and_hms(2024, 3, 30, 12, 0, 0)
+                .unwrap(),
+        );
+        assert!(matches!(
+            utc.render(parse_strict("2024-03-31 2:30 Europe/Warsaw").unwrap()),
+            Err(OrgModeDateTimeError::NonexistentLocalTime(_))
+        ));
+    }
+
+    #[test]
+    fn test_ranges() {
+        let renderer = ZonedRenderer::new(warsaw(2024, 6, 13, 8, 0));
+        assert_eq!(
+            rendered(&renderer, "fri 22:00-1:00").unwrap(),
+            ZonedRenderedSpec::DateTimeRange(warsaw(2024, 6, 14, 22, 0), warsaw(2024, 6, 15, 1, 0))
+        );
+        assert_eq!(
+            rendered(&renderer, "9:00-10:00").unwrap(),
+            ZonedRenderedSpec::TimeRange(
+                NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                NaiveTime::from_hms_opt(10, 0, 0).unwrap()
+            )
+        );
+    }
+}